@@ -0,0 +1,64 @@
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (1, 'James', 'Carter');
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (2, 'Helen', 'Leary');
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (3, 'Linda', 'Douglas');
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (4, 'Rafael', 'Ortega');
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (5, 'Henry', 'Stevens');
+INSERT OR IGNORE INTO vets (id, first_name, last_name) VALUES (6, 'Sharon', 'Jenkins');
+
+INSERT OR IGNORE INTO specialties VALUES (1, 'radiology');
+INSERT OR IGNORE INTO specialties VALUES (2, 'surgery');
+INSERT OR IGNORE INTO specialties VALUES (3, 'dentistry');
+
+INSERT OR IGNORE INTO vet_specialties VALUES (2, 1);
+INSERT OR IGNORE INTO vet_specialties VALUES (3, 2);
+INSERT OR IGNORE INTO vet_specialties VALUES (3, 3);
+INSERT OR IGNORE INTO vet_specialties VALUES (4, 2);
+INSERT OR IGNORE INTO vet_specialties VALUES (5, 1);
+
+INSERT OR IGNORE INTO types VALUES (1, 'cat');
+INSERT OR IGNORE INTO types VALUES (2, 'dog');
+INSERT OR IGNORE INTO types VALUES (3, 'lizard');
+INSERT OR IGNORE INTO types VALUES (4, 'snake');
+INSERT OR IGNORE INTO types VALUES (5, 'bird');
+INSERT OR IGNORE INTO types VALUES (6, 'hamster');
+
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (1, 'George', 'Franklin', '110 W. Liberty St.', 'Madison');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (2, 'Betty', 'Davis', '638 Cardinal Ave.', 'Sun Prairie');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (3, 'Eduardo', 'Rodriquez', '2693 Commerce St.', 'McFarland');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (4, 'Harold', 'Davis', '563 Friendly St.', 'Windsor');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (5, 'Peter', 'McTavish', '2387 S. Fair Way', 'Madison');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (6, 'Jean', 'Coleman', '105 N. Lake St.', 'Monona');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (7, 'Jeff', 'Black', '1450 Oak Blvd.', 'Monona');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (8, 'Maria', 'Escobito', '345 Maple St.', 'Madison');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (9, 'David', 'Schroeder', '2749 Blackhawk Trail', 'Madison');
+INSERT OR IGNORE INTO owners (id, first_name, last_name, address, city) VALUES (10, 'Carlos', 'Estaban', '2335 Independence La.', 'Waunakee');
+
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (1, 1, 'home', '+16085551023');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (2, 2, 'home', '+16085551749');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (3, 3, 'home', '+16085558763');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (4, 4, 'home', '+16085553198');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (5, 5, 'home', '+16085552765');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (6, 6, 'home', '+16085552654');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (7, 7, 'home', '+16085555387');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (8, 8, 'home', '+16085557683');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (9, 9, 'home', '+16085559435');
+INSERT OR IGNORE INTO owner_phones (id, owner_id, kind, number) VALUES (10, 10, 'home', '+16085555487');
+
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (1, 'Leo', '2000-09-07', 1, 1);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (2, 'Basil', '2002-08-06', 6, 2);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (3, 'Rosy', '2001-04-17', 2, 3);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (4, 'Jewel', '2000-03-07', 2, 3);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (5, 'Iggy', '2000-11-30', 3, 4);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (6, 'George', '2000-01-20', 4, 5);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (7, 'Samantha', '1995-09-04', 1, 6);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (8, 'Max', '1995-09-04', 1, 6);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (9, 'Lucky', '1999-08-06', 5, 7);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (10, 'Mulligan', '1997-02-24', 2, 8);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (11, 'Freddy', '2000-03-09', 5, 9);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (12, 'Lucky', '2000-06-24', 2, 10);
+INSERT OR IGNORE INTO pets (id, name, birth_date, type_id, owner_id) VALUES (13, 'Sly', '2002-06-08', 1, 10);
+
+INSERT OR IGNORE INTO visits (id, pet_id, visit_date, description) VALUES (1, 7, '2010-03-04', 'rabies shot');
+INSERT OR IGNORE INTO visits (id, pet_id, visit_date, description) VALUES (2, 8, '2011-03-04', 'rabies shot');
+INSERT OR IGNORE INTO visits (id, pet_id, visit_date, description) VALUES (3, 8, '2009-06-04', 'neutered');
+INSERT OR IGNORE INTO visits (id, pet_id, visit_date, description) VALUES (4, 7, '2008-09-04', 'spayed');
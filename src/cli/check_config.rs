@@ -0,0 +1,166 @@
+use std::io;
+
+use sea_orm::{ConnectionTrait, Database};
+
+use crate::{
+    config::{
+        app::{AppConfig, PathConfig, StorageBackend},
+        cookie::SigningKeys,
+        error::ConfigError,
+        i18n::{I18n, DEFAULT_LANGUAGE},
+        tera,
+    },
+    storage,
+};
+
+struct Check {
+    name: &'static str,
+    result: Result<String, String>,
+}
+
+pub async fn run(app_config: Result<AppConfig, ConfigError>) -> io::Result<()> {
+    // 설정이 잘못되어도 나머지 항목은 기본 경로로 점검함
+    let default_paths = PathConfig::default();
+    let (paths, default_language) = match &app_config {
+        Ok(config) => (&config.paths, config.i18n.default_language.as_str()),
+        Err(_) => (&default_paths, DEFAULT_LANGUAGE),
+    };
+
+    let checks = [
+        check_config(&app_config),
+        check_signing_key(&app_config),
+        check_templates(paths),
+        check_locales(paths, default_language),
+        check_storage(&app_config),
+        check_database(&app_config).await,
+    ];
+
+    let mut failed = 0;
+    for Check { name, result } in &checks {
+        match result {
+            Ok(detail) => println!("[ OK ] {name:<10} {detail}"),
+            Err(reason) => {
+                failed += 1;
+                println!("[FAIL] {name:<10} {reason}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(io::Error::other(format!("{failed} check(s) failed")));
+    }
+
+    Ok(())
+}
+
+fn check_config(app_config: &Result<AppConfig, ConfigError>) -> Check {
+    let result = app_config
+        .as_ref()
+        .map(|config| {
+            let scheme = config.database.url.split(':').next().unwrap_or_default();
+            format!(
+                "bind {}, database {scheme}, max connections {}",
+                config.server.bind_address,
+                config
+                    .database
+                    .max_connections
+                    .map_or("default".to_string(), |max| max.to_string())
+            )
+        })
+        .map_err(ToString::to_string);
+
+    Check {
+        name: "config",
+        result,
+    }
+}
+
+fn check_signing_key(app_config: &Result<AppConfig, ConfigError>) -> Check {
+    let result = match app_config {
+        Err(_) => Err("skipped because the configuration is invalid".to_string()),
+        Ok(config) => SigningKeys::load(&config.security, config.server.environment)
+            .map(|keys| {
+                let security = &config.security;
+                if security.signing_key.is_none() && security.signing_key_file.is_none() {
+                    "no signing key configured, a random key is used per start".to_string()
+                } else if keys.fallback.is_some() {
+                    "signing key and fallback key loaded".to_string()
+                } else {
+                    "signing key loaded".to_string()
+                }
+            })
+            .map_err(|e| e.to_string()),
+    };
+
+    Check {
+        name: "cookies",
+        result,
+    }
+}
+
+fn check_templates(paths: &PathConfig) -> Check {
+    let result = tera::try_init(paths)
+        .map(|tera| format!("{} templates loaded", tera.get_template_names().count()))
+        .map_err(|e| tera::describe_error(&e));
+
+    Check {
+        name: "templates",
+        result,
+    }
+}
+
+fn check_locales(paths: &PathConfig, default_language: &str) -> Check {
+    let result = I18n::try_new(&paths.locales, default_language)
+        .map_err(|e| e.to_string())
+        .and_then(|i18n| {
+            let missing = i18n.missing_languages();
+            if missing.is_empty() {
+                Ok(format!("{} languages loaded", i18n.language_count()))
+            } else {
+                Err(format!("missing translations for {}", missing.join(", ")))
+            }
+        });
+
+    Check {
+        name: "locales",
+        result,
+    }
+}
+
+fn check_storage(app_config: &Result<AppConfig, ConfigError>) -> Check {
+    let result = match app_config {
+        Err(_) => Err("skipped because the configuration is invalid".to_string()),
+        Ok(config) => storage::init(&config.storage)
+            .map(|_| match config.storage.backend {
+                StorageBackend::Local => {
+                    format!("local directory {}", config.storage.root.display())
+                }
+                StorageBackend::Memory => "memory, files are lost on restart".to_string(),
+            })
+            .map_err(|e| e.to_string()),
+    };
+
+    Check {
+        name: "storage",
+        result,
+    }
+}
+
+async fn check_database(app_config: &Result<AppConfig, ConfigError>) -> Check {
+    let result = match app_config {
+        Err(_) => Err("skipped because the configuration is invalid".to_string()),
+        Ok(config) => match Database::connect(config.database.connect_options()).await {
+            Err(e) => Err(e.to_string()),
+            Ok(conn) => conn
+                .ping()
+                .await
+                .map(|_| format!("connected to {:?}", conn.get_database_backend()))
+                .map_err(|e| e.to_string()),
+        },
+    };
+
+    Check {
+        name: "database",
+        result,
+    }
+}
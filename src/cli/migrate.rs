@@ -0,0 +1,56 @@
+use std::io;
+
+use clap::{Args, Subcommand};
+use sea_orm::{DbConn, DbErr};
+use sea_orm_migration::MigratorTrait;
+
+use crate::{
+    config::{self, app::AppConfig},
+    migration::Migrator,
+};
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    #[command(subcommand)]
+    command: Option<MigrateCommand>,
+}
+
+#[derive(Subcommand)]
+enum MigrateCommand {
+    /// Apply pending migrations (default)
+    Up {
+        /// Number of pending migrations to apply
+        #[arg(short = 'n', long)]
+        steps: Option<u32>,
+    },
+    /// Roll back applied migrations
+    Down {
+        /// Number of applied migrations to roll back
+        #[arg(short = 'n', long, default_value_t = 1)]
+        steps: u32,
+    },
+    /// Show which migrations have been applied
+    Status,
+}
+
+pub async fn run(app_config: &AppConfig, args: MigrateArgs) -> io::Result<()> {
+    let conn = config::db::connect_db(&app_config.database)
+        .await
+        .map_err(io::Error::other)?;
+
+    let result = match args.command.unwrap_or(MigrateCommand::Up { steps: None }) {
+        MigrateCommand::Up { steps } => Migrator::up(&conn, steps).await,
+        MigrateCommand::Down { steps } => Migrator::down(&conn, Some(steps)).await,
+        MigrateCommand::Status => print_status(&conn).await,
+    };
+
+    result.map_err(io::Error::other)
+}
+
+async fn print_status(conn: &DbConn) -> Result<(), DbErr> {
+    for migration in Migrator::get_migration_with_status(conn).await? {
+        println!("{:<8} {}", migration.status().to_string(), migration.name());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+pub mod check_config;
+pub mod migrate;
+pub mod routes;
+pub mod seed;
+
+#[derive(Parser)]
+#[command(version, about = "Spring Petclinic sample application on actix-web")]
+pub struct Cli {
+    /// TOML config file (defaults to $PETCLINIC_CONFIG, then ./petclinic.toml if present)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Start the web server (default)
+    Serve,
+    /// Apply, roll back or inspect database migrations
+    Migrate(migrate::MigrateArgs),
+    /// Load sample data or generate demo data
+    Seed(seed::SeedArgs),
+    /// Validate configuration, templates, locales and database connectivity
+    CheckConfig,
+    /// Print the routes registered by the web layer
+    Routes,
+    /// Print a new random cookie signing key
+    GenerateKey,
+}
@@ -0,0 +1,7 @@
+use crate::web::ROUTES;
+
+pub fn run() {
+    for route in ROUTES {
+        println!("{:<6} {:<45} {}", route.method, route.path, route.handler);
+    }
+}
@@ -0,0 +1,156 @@
+use std::{fs, io, path::PathBuf};
+
+use chrono::{Days, Local, TimeDelta};
+use clap::Args;
+use sea_orm::{ConnectionTrait, DbConn};
+
+use crate::{
+    config::{
+        self,
+        app::{AppConfig, SchedulingConfig},
+    },
+    domain::owner::{owner_phone::PhoneKind, owners::ContactMethod},
+    service::{
+        owner_service::{ContactDetails, OwnerService},
+        pet_service::PetService,
+        pet_type_service::PetTypeService,
+        vet_service::VetService,
+        visit_service::{Appointment, VisitService},
+    },
+};
+
+const FIRST_NAMES: [&str; 10] = [
+    "Olivia", "Liam", "Emma", "Noah", "Ava", "Elijah", "Sophia", "James", "Mia", "Lucas",
+];
+const LAST_NAMES: [&str; 10] = [
+    "Anderson", "Brooks", "Carter", "Dalton", "Ellis", "Foster", "Gray", "Hayes", "Irving",
+    "Jordan",
+];
+const STREETS: [&str; 5] = [
+    "Oak St.",
+    "Maple Ave.",
+    "Pine Rd.",
+    "Cedar Ln.",
+    "Elm Blvd.",
+];
+const CITIES: [&str; 5] = ["Madison", "Monona", "Middleton", "Verona", "Waunakee"];
+const PET_NAMES: [&str; 10] = [
+    "Bella", "Charlie", "Luna", "Milo", "Daisy", "Oscar", "Coco", "Rocky", "Nala", "Simba",
+];
+const VISIT_DESCRIPTIONS: [&str; 4] = [
+    "rabies shot",
+    "annual checkup",
+    "dental cleaning",
+    "neutered",
+];
+
+#[derive(Args)]
+pub struct SeedArgs {
+    /// SQL file to load instead of the bundled sample data
+    #[arg(long, conflicts_with = "generate")]
+    file: Option<PathBuf>,
+    /// Number of demo owners to generate, each with pets and visits
+    #[arg(long)]
+    generate: Option<usize>,
+}
+
+pub async fn run(app_config: &AppConfig, args: SeedArgs) -> io::Result<()> {
+    let conn = config::db::connect_db(&app_config.database)
+        .await
+        .map_err(io::Error::other)?;
+
+    match (args.file, args.generate) {
+        (_, Some(count)) => generate(&conn, &app_config.scheduling, count).await,
+        (Some(path), None) => {
+            let sql = fs::read_to_string(path)?;
+            execute(&conn, &sql).await
+        }
+        (None, None) => {
+            let sql = config::db::sample_data(conn.get_database_backend());
+            execute(&conn, sql).await
+        }
+    }
+}
+
+async fn execute(conn: &DbConn, sql: &str) -> io::Result<()> {
+    conn.execute_unprepared(sql)
+        .await
+        .map_err(io::Error::other)?;
+
+    Ok(())
+}
+
+async fn generate(conn: &DbConn, scheduling: &SchedulingConfig, count: usize) -> io::Result<()> {
+    let pet_types = PetTypeService::fetch_all_pet_types(conn)
+        .await
+        .map_err(io::Error::other)?;
+    if pet_types.is_empty() {
+        return Err(io::Error::other(
+            "no pet types found; run `seed` without --generate first",
+        ));
+    }
+
+    let vets = VetService::fetch_active_vets(conn)
+        .await
+        .map_err(io::Error::other)?;
+
+    let today = Local::now().date_naive();
+
+    for i in 0..count {
+        let owner = OwnerService::save_owner(
+            conn,
+            Some(FIRST_NAMES[i % FIRST_NAMES.len()].to_string()),
+            Some(LAST_NAMES[(i / FIRST_NAMES.len()) % LAST_NAMES.len()].to_string()),
+            Some(format!("{} {}", 100 + i, STREETS[i % STREETS.len()])),
+            Some(CITIES[i % CITIES.len()].to_string()),
+            ContactDetails {
+                email: None,
+                phones: vec![(PhoneKind::Home, format!("+1608555{:04}", i % 10_000))],
+                preferred_contact: ContactMethod::Phone,
+                emergency_contact_name: None,
+                emergency_contact_phone: None,
+            },
+        )
+        .await
+        .map_err(io::Error::other)?;
+
+        for j in 0..=(i % 2) {
+            let seq = i * 2 + j;
+            let pet = PetService::save_pet(
+                conn,
+                Some(PET_NAMES[seq % PET_NAMES.len()].to_string()),
+                today.checked_sub_days(Days::new(365 * (1 + seq as u64 % 12))),
+                pet_types[seq % pet_types.len()].id,
+                Some(owner.id),
+            )
+            .await
+            .map_err(io::Error::other)?;
+
+            let Some(vet) = vets.get(seq % vets.len().max(1)) else {
+                continue;
+            };
+            // 과거 visit이므로 예약 충돌 검사 없이 오전 slot에 나눠 넣음
+            let day = today - Days::new(30 * (1 + j as u64));
+            let starts_at = day.and_time(scheduling.opening_time)
+                + TimeDelta::minutes(i64::from(scheduling.slot_minutes) * (i % 4) as i64);
+
+            VisitService::save_visit(
+                conn,
+                Some(pet.id),
+                Some(VISIT_DESCRIPTIONS[seq % VISIT_DESCRIPTIONS.len()].to_string()),
+                Appointment {
+                    starts_at,
+                    duration_minutes: scheduling.slot_minutes as i32,
+                    vet_id: vet.id,
+                    room: scheduling.rooms[seq % scheduling.rooms.len()].clone(),
+                },
+            )
+            .await
+            .map_err(io::Error::other)?;
+        }
+    }
+
+    println!("Generated {count} owners with pets and visits");
+
+    Ok(())
+}
@@ -0,0 +1,797 @@
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    net::ToSocketAddrs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use chrono::NaiveTime;
+use serde::Deserialize;
+
+use crate::model::phone::is_known_country_code;
+
+use super::{
+    db::DbConfig,
+    error::ConfigError,
+    i18n::{ALLOWED_LANGUAGES, DEFAULT_LANGUAGE},
+    log::LogConfig,
+};
+
+const DEFAULT_CONFIG_FILE: &str = "petclinic.toml";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub paths: PathConfig,
+    pub i18n: I18nConfig,
+    pub pagination: PaginationConfig,
+    pub scheduling: SchedulingConfig,
+    pub vaccinations: VaccinationConfig,
+    pub billing: BillingConfig,
+    pub contacts: ContactConfig,
+    pub photos: PhotoConfig,
+    pub attachments: AttachmentConfig,
+    pub storage: StorageConfig,
+    pub security: SecurityConfig,
+    pub log: LogConfig,
+    pub database: DbConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub environment: Environment,
+    pub bind_address: String,
+    pub workers: Option<usize>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            environment: Environment::Development,
+            bind_address: "127.0.0.1:8080".to_string(),
+            workers: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    Development,
+    Production,
+}
+
+impl FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "development" => Ok(Environment::Development),
+            "production" => Ok(Environment::Production),
+            _ => Err("must be development or production".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathConfig {
+    pub templates: PathBuf,
+    pub locales: PathBuf,
+    pub static_files: PathBuf,
+}
+
+impl Default for PathConfig {
+    fn default() -> Self {
+        PathConfig {
+            templates: PathBuf::from("templates"),
+            locales: PathBuf::from("locales"),
+            static_files: PathBuf::from("static"),
+        }
+    }
+}
+
+impl PathConfig {
+    pub fn template_glob(&self) -> String {
+        format!("{}/**/*.html", self.templates.display())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct I18nConfig {
+    pub default_language: String,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        I18nConfig {
+            default_language: DEFAULT_LANGUAGE.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaginationConfig {
+    pub page_size: u64,
+    /// query string의 size로 요청할 수 있는 최대 크기
+    pub max_page_size: u64,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        PaginationConfig {
+            page_size: 5,
+            max_page_size: 100,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulingConfig {
+    /// vet 한 명이 하루에 받을 수 있는 visit 수. 이 수만큼 예약된 vet은 해당 날짜에 선택할 수 없음
+    pub max_visits_per_vet_per_day: u64,
+    pub opening_time: NaiveTime,
+    pub closing_time: NaiveTime,
+    /// 예약 시작 시각의 간격이자 빈 시간을 찾을 때 이동하는 단위
+    pub slot_minutes: u32,
+    pub rooms: Vec<String>,
+}
+
+impl Default for SchedulingConfig {
+    fn default() -> Self {
+        SchedulingConfig {
+            max_visits_per_vet_per_day: 8,
+            opening_time: NaiveTime::from_hms_opt(9, 0, 0).expect("valid time"),
+            closing_time: NaiveTime::from_hms_opt(17, 0, 0).expect("valid time"),
+            slot_minutes: 30,
+            rooms: vec!["Exam 1".to_string(), "Exam 2".to_string()],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VaccinationConfig {
+    /// 다음 접종일이 오늘부터 이 일수 안에 있으면 곧 접종할 대상으로 표시함
+    pub due_soon_days: u32,
+}
+
+impl Default for VaccinationConfig {
+    fn default() -> Self {
+        VaccinationConfig { due_soon_days: 30 }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BillingConfig {
+    /// 화면과 invoice에 금액과 함께 표시하는 통화 코드
+    pub currency: String,
+    /// 새 invoice에 적용하는 세율. 1 basis point는 0.01%이며 이미 발행한 invoice에는 영향이 없음
+    pub tax_rate_basis_points: u32,
+}
+
+impl Default for BillingConfig {
+    fn default() -> Self {
+        BillingConfig {
+            currency: "USD".to_string(),
+            tax_rate_basis_points: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContactConfig {
+    /// '+'나 '00' 없이 입력한 국내 전화번호에 붙이는 국가 번호
+    pub default_country_code: String,
+}
+
+impl Default for ContactConfig {
+    fn default() -> Self {
+        ContactConfig {
+            default_country_code: "1".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhotoConfig {
+    /// 업로드할 수 있는 pet 사진 파일의 최대 크기(bytes)
+    pub max_bytes: usize,
+    /// 저장하는 사진의 긴 변 길이. 이보다 큰 사진은 줄여서 저장함
+    pub display_size: u32,
+    /// owner 상세 화면에 보여주는 thumbnail의 긴 변 길이
+    pub thumbnail_size: u32,
+}
+
+impl Default for PhotoConfig {
+    fn default() -> Self {
+        PhotoConfig {
+            max_bytes: 5 * 1024 * 1024,
+            display_size: 800,
+            thumbnail_size: 160,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttachmentConfig {
+    /// visit에 첨부할 수 있는 파일 하나의 최대 크기(bytes)
+    pub max_bytes: usize,
+}
+
+impl Default for AttachmentConfig {
+    fn default() -> Self {
+        AttachmentConfig {
+            max_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+/// 업로드한 파일을 저장하는 곳. local 저장소의 root는 `/static`으로 공개되지 않도록 static 디렉터리 밖이어야 함
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    pub root: PathBuf,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig {
+            backend: StorageBackend::Local,
+            root: PathBuf::from("uploads"),
+        }
+    }
+}
+
+/// memory 저장소는 재시작하면 파일이 모두 사라지므로 개발과 test에서만 씀
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    Local,
+    Memory,
+}
+
+impl FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "local" => Ok(StorageBackend::Local),
+            "memory" => Ok(StorageBackend::Memory),
+            _ => Err("must be local or memory".to_string()),
+        }
+    }
+}
+
+/// 값을 직접 넣거나 파일 경로를 지정하며, 둘 다 base64로 인코딩한 64 bytes 이상의 key여야 함
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityConfig {
+    pub signing_key: Option<String>,
+    pub signing_key_file: Option<PathBuf>,
+    pub fallback_signing_key: Option<String>,
+    pub fallback_signing_key_file: Option<PathBuf>,
+}
+
+impl AppConfig {
+    /// 기본값 위에 TOML 파일과 환경 변수를 차례로 덮어써서 설정을 구성함
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| env::var("PETCLINIC_CONFIG").ok().map(PathBuf::from))
+            .or_else(|| {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                default_path.exists().then_some(default_path)
+            });
+
+        let mut config = match path {
+            Some(path) => Self::from_file(&path)?,
+            None => AppConfig::default(),
+        };
+        config.apply_env(&|key| env::var(key).ok())?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content = fs::read_to_string(path).map_err(|e| ConfigError::Read {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+
+        toml::from_str(&content).map_err(|e| ConfigError::Parse {
+            path: path.display().to_string(),
+            reason: e.message().to_string(),
+        })
+    }
+
+    fn apply_env(&mut self, lookup: &impl Fn(&str) -> Option<String>) -> Result<(), ConfigError> {
+        override_var(lookup, "PETCLINIC_ENV", &mut self.server.environment)?;
+        override_var(lookup, "PETCLINIC_LOG_FORMAT", &mut self.log.format)?;
+        if let Some(endpoint) = parse_var(lookup, "PETCLINIC_OTLP_ENDPOINT")? {
+            self.log.otlp_endpoint = Some(endpoint);
+        }
+        override_var(lookup, "PETCLINIC_SERVICE_NAME", &mut self.log.service_name)?;
+        override_var(
+            lookup,
+            "PETCLINIC_BIND_ADDRESS",
+            &mut self.server.bind_address,
+        )?;
+        if let Some(workers) = parse_var(lookup, "PETCLINIC_WORKERS")? {
+            self.server.workers = Some(workers);
+        }
+        override_var(lookup, "PETCLINIC_TEMPLATES_DIR", &mut self.paths.templates)?;
+        override_var(lookup, "PETCLINIC_LOCALES_DIR", &mut self.paths.locales)?;
+        override_var(lookup, "PETCLINIC_STATIC_DIR", &mut self.paths.static_files)?;
+        override_var(
+            lookup,
+            "PETCLINIC_DEFAULT_LANGUAGE",
+            &mut self.i18n.default_language,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_PAGE_SIZE",
+            &mut self.pagination.page_size,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_MAX_PAGE_SIZE",
+            &mut self.pagination.max_page_size,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_MAX_VISITS_PER_VET_PER_DAY",
+            &mut self.scheduling.max_visits_per_vet_per_day,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_OPENING_TIME",
+            &mut self.scheduling.opening_time,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_CLOSING_TIME",
+            &mut self.scheduling.closing_time,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_SLOT_MINUTES",
+            &mut self.scheduling.slot_minutes,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_VACCINATION_DUE_SOON_DAYS",
+            &mut self.vaccinations.due_soon_days,
+        )?;
+        override_var(lookup, "PETCLINIC_CURRENCY", &mut self.billing.currency)?;
+        override_var(
+            lookup,
+            "PETCLINIC_TAX_RATE_BASIS_POINTS",
+            &mut self.billing.tax_rate_basis_points,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_DEFAULT_COUNTRY_CODE",
+            &mut self.contacts.default_country_code,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_PHOTO_MAX_BYTES",
+            &mut self.photos.max_bytes,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_ATTACHMENT_MAX_BYTES",
+            &mut self.attachments.max_bytes,
+        )?;
+        override_var(
+            lookup,
+            "PETCLINIC_STORAGE_BACKEND",
+            &mut self.storage.backend,
+        )?;
+        override_var(lookup, "PETCLINIC_STORAGE_ROOT", &mut self.storage.root)?;
+        if let Some(rooms) = lookup("PETCLINIC_ROOMS").filter(|v| !v.trim().is_empty()) {
+            self.scheduling.rooms = rooms
+                .split(',')
+                .map(|room| room.trim().to_string())
+                .filter(|room| !room.is_empty())
+                .collect();
+        }
+        for (key, target) in [
+            ("PETCLINIC_SIGNING_KEY", &mut self.security.signing_key),
+            (
+                "PETCLINIC_FALLBACK_SIGNING_KEY",
+                &mut self.security.fallback_signing_key,
+            ),
+        ] {
+            if let Some(value) = parse_var(lookup, key)? {
+                *target = Some(value);
+            }
+        }
+        for (key, target) in [
+            (
+                "PETCLINIC_SIGNING_KEY_FILE",
+                &mut self.security.signing_key_file,
+            ),
+            (
+                "PETCLINIC_FALLBACK_SIGNING_KEY_FILE",
+                &mut self.security.fallback_signing_key_file,
+            ),
+        ] {
+            if let Some(value) = parse_var(lookup, key)? {
+                *target = Some(value);
+            }
+        }
+
+        self.database.apply_env(lookup)
+    }
+
+    /// 화면을 그리는 serve와 check-config만 template, locale, static 디렉터리가 필요하므로
+    /// migrate나 seed는 이 검사 없이 실행할 수 있음
+    pub fn validate_paths(&self) -> Result<(), ConfigError> {
+        for (key, dir) in [
+            ("paths.templates", &self.paths.templates),
+            ("paths.locales", &self.paths.locales),
+            ("paths.static_files", &self.paths.static_files),
+        ] {
+            if !dir.is_dir() {
+                return Err(ConfigError::Invalid {
+                    key,
+                    value: dir.display().to_string(),
+                    reason: "directory does not exist".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let bind_address = &self.server.bind_address;
+        if bind_address
+            .to_socket_addrs()
+            .map_or(true, |mut addrs| addrs.next().is_none())
+        {
+            return Err(ConfigError::Invalid {
+                key: "server.bind_address",
+                value: bind_address.clone(),
+                reason: "must be a resolvable host:port".to_string(),
+            });
+        }
+
+        if self.server.workers == Some(0) {
+            return Err(ConfigError::Invalid {
+                key: "server.workers",
+                value: "0".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        if !ALLOWED_LANGUAGES.contains(&self.i18n.default_language.as_str()) {
+            return Err(ConfigError::Invalid {
+                key: "i18n.default_language",
+                value: self.i18n.default_language.clone(),
+                reason: format!("must be one of {}", ALLOWED_LANGUAGES.join(", ")),
+            });
+        }
+
+        if let Some(endpoint) = &self.log.otlp_endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                return Err(ConfigError::Invalid {
+                    key: "log.otlp_endpoint",
+                    value: endpoint.clone(),
+                    reason: "must be an http:// or https:// URL".to_string(),
+                });
+            }
+        }
+
+        if self.pagination.page_size == 0 {
+            return Err(ConfigError::Invalid {
+                key: "pagination.page_size",
+                value: "0".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        if self.pagination.max_page_size < self.pagination.page_size {
+            return Err(ConfigError::Invalid {
+                key: "pagination.max_page_size",
+                value: self.pagination.max_page_size.to_string(),
+                reason: format!(
+                    "must not be less than pagination.page_size ({})",
+                    self.pagination.page_size
+                ),
+            });
+        }
+
+        if self.scheduling.max_visits_per_vet_per_day == 0 {
+            return Err(ConfigError::Invalid {
+                key: "scheduling.max_visits_per_vet_per_day",
+                value: "0".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        let scheduling = &self.scheduling;
+        if scheduling.opening_time >= scheduling.closing_time {
+            return Err(ConfigError::Invalid {
+                key: "scheduling.closing_time",
+                value: scheduling.closing_time.to_string(),
+                reason: "must be later than scheduling.opening_time".to_string(),
+            });
+        }
+        if scheduling.slot_minutes == 0 {
+            return Err(ConfigError::Invalid {
+                key: "scheduling.slot_minutes",
+                value: "0".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+        if scheduling.rooms.is_empty() {
+            return Err(ConfigError::Invalid {
+                key: "scheduling.rooms",
+                value: "[]".to_string(),
+                reason: "must list at least one room".to_string(),
+            });
+        }
+
+        if self.vaccinations.due_soon_days == 0 {
+            return Err(ConfigError::Invalid {
+                key: "vaccinations.due_soon_days",
+                value: "0".to_string(),
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+
+        if self.billing.currency.trim().is_empty() {
+            return Err(ConfigError::Invalid {
+                key: "billing.currency",
+                value: self.billing.currency.clone(),
+                reason: "must not be empty".to_string(),
+            });
+        }
+        if self.billing.tax_rate_basis_points > 10_000 {
+            return Err(ConfigError::Invalid {
+                key: "billing.tax_rate_basis_points",
+                value: self.billing.tax_rate_basis_points.to_string(),
+                reason: "must be at most 10000 (100%)".to_string(),
+            });
+        }
+
+        let country_code = &self.contacts.default_country_code;
+        let is_country_code = (1..=3).contains(&country_code.len())
+            && country_code.bytes().all(|b| b.is_ascii_digit())
+            && !country_code.starts_with('0');
+        if !is_country_code || !is_known_country_code(country_code) {
+            return Err(ConfigError::Invalid {
+                key: "contacts.default_country_code",
+                value: country_code.clone(),
+                reason: "must be a country calling code such as 1 or 44".to_string(),
+            });
+        }
+
+        let photos = &self.photos;
+        for (key, value) in [
+            ("photos.max_bytes", photos.max_bytes as u64),
+            ("photos.display_size", u64::from(photos.display_size)),
+            ("photos.thumbnail_size", u64::from(photos.thumbnail_size)),
+            ("attachments.max_bytes", self.attachments.max_bytes as u64),
+        ] {
+            if value == 0 {
+                return Err(ConfigError::Invalid {
+                    key,
+                    value: "0".to_string(),
+                    reason: "must be greater than 0".to_string(),
+                });
+            }
+        }
+        if photos.thumbnail_size > photos.display_size {
+            return Err(ConfigError::Invalid {
+                key: "photos.thumbnail_size",
+                value: photos.thumbnail_size.to_string(),
+                reason: "must not be larger than photos.display_size".to_string(),
+            });
+        }
+
+        // static 디렉터리 아래에 저장하면 파일 목록과 함께 누구나 내려받을 수 있음
+        if self.storage.backend == StorageBackend::Local
+            && self.storage.root.starts_with(&self.paths.static_files)
+        {
+            return Err(ConfigError::Invalid {
+                key: "storage.root",
+                value: self.storage.root.display().to_string(),
+                reason: "must not be inside paths.static_files".to_string(),
+            });
+        }
+
+        self.database.validate()
+    }
+}
+
+pub(super) fn parse_var<T>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    key: &'static str,
+) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lookup(key)
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|e: T::Err| ConfigError::Invalid {
+                    key,
+                    reason: e.to_string(),
+                    value,
+                })
+        })
+        .transpose()
+}
+
+pub(super) fn override_var<T>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    key: &'static str,
+    target: &mut T,
+) -> Result<(), ConfigError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if let Some(value) = parse_var(lookup, key)? {
+        *target = value;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lookup_from(vars: &[(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+
+        move |key| vars.get(key).map(|v| v.to_string())
+    }
+
+    #[test]
+    fn test_toml_then_env_overrides() {
+        let mut config: AppConfig = toml::from_str(
+            r#"
+            [server]
+            bind_address = "0.0.0.0:9090"
+            workers = 4
+
+            [pagination]
+            page_size = 10
+
+            [database]
+            url = "sqlite::memory:"
+            max_connections = 8
+            "#,
+        )
+        .unwrap();
+
+        config
+            .apply_env(&lookup_from(&[
+                ("PETCLINIC_PAGE_SIZE", "20"),
+                ("DATABASE_MAX_CONNECTIONS", "16"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.server.bind_address, "0.0.0.0:9090");
+        assert_eq!(config.server.workers, Some(4));
+        assert_eq!(config.pagination.page_size, 20);
+        assert_eq!(config.i18n.default_language, DEFAULT_LANGUAGE);
+        assert_eq!(config.database.url, "sqlite::memory:");
+        assert_eq!(config.database.max_connections, Some(16));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_paths_are_checked_separately() {
+        let mut config = AppConfig::default();
+        config
+            .apply_env(&lookup_from(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_TEMPLATES_DIR", "does-not-exist"),
+            ]))
+            .unwrap();
+
+        // migrate, seed는 template 없이도 실행할 수 있어야 함
+        assert!(config.validate().is_ok());
+        assert!(matches!(
+            config.validate_paths(),
+            Err(ConfigError::Invalid {
+                key: "paths.templates",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_key_is_rejected() {
+        let result = toml::from_str::<AppConfig>("[server]\nport = 8080\n");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let invalid_key = |vars| {
+            let mut config = AppConfig::default();
+            config.apply_env(&lookup_from(vars))?;
+            config.validate()?;
+            config.validate_paths()
+        };
+
+        assert!(matches!(
+            invalid_key(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_WORKERS", "many")
+            ]),
+            Err(ConfigError::Invalid {
+                key: "PETCLINIC_WORKERS",
+                ..
+            })
+        ));
+        assert!(matches!(
+            invalid_key(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_DEFAULT_LANGUAGE", "fr")
+            ]),
+            Err(ConfigError::Invalid {
+                key: "i18n.default_language",
+                ..
+            })
+        ));
+        assert!(matches!(
+            invalid_key(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_LOCALES_DIR", "does-not-exist")
+            ]),
+            Err(ConfigError::Invalid {
+                key: "paths.locales",
+                ..
+            })
+        ));
+        assert!(matches!(
+            invalid_key(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_STORAGE_ROOT", "static/uploads")
+            ]),
+            Err(ConfigError::Invalid {
+                key: "storage.root",
+                ..
+            })
+        ));
+        assert!(matches!(
+            invalid_key(&[
+                ("DATABASE_URL", "sqlite::memory:"),
+                ("PETCLINIC_MAX_PAGE_SIZE", "2")
+            ]),
+            Err(ConfigError::Invalid {
+                key: "pagination.max_page_size",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{fs, path::Path};
+
+use actix_web::{cookie::Key, dev::ResponseHead, HttpRequest};
+use actix_web_flash_messages::{
+    storage::{CookieMessageStore, FlashMessageStore, LoadError, StoreError},
+    FlashMessage,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::{
+    app::{Environment, SecurityConfig},
+    error::ConfigError,
+};
+
+// cookie::Key는 서명과 암호화에 각각 32 bytes를 쓰므로 최소 64 bytes가 필요함
+const MIN_KEY_LEN: usize = 64;
+
+pub struct SigningKeys {
+    pub primary: Key,
+    pub fallback: Option<Key>,
+}
+
+impl SigningKeys {
+    pub fn load(config: &SecurityConfig, environment: Environment) -> Result<Self, ConfigError> {
+        let primary = read_key(
+            "security.signing_key",
+            config.signing_key.as_deref(),
+            config.signing_key_file.as_deref(),
+        )?;
+        let fallback = read_key(
+            "security.fallback_signing_key",
+            config.fallback_signing_key.as_deref(),
+            config.fallback_signing_key_file.as_deref(),
+        )?;
+
+        let primary = match (primary, environment) {
+            (Some(key), _) => key,
+            (None, Environment::Production) => {
+                return Err(ConfigError::Missing {
+                    key: "security.signing_key",
+                })
+            }
+            (None, Environment::Development) => {
+                log::warn!(
+                    "no cookie signing key configured, flash messages will not survive a restart"
+                );
+                Key::generate()
+            }
+        };
+
+        Ok(SigningKeys { primary, fallback })
+    }
+
+    pub fn message_store(&self) -> RotatingCookieMessageStore {
+        RotatingCookieMessageStore {
+            primary: CookieMessageStore::builder(self.primary.clone()).build(),
+            fallback: self
+                .fallback
+                .clone()
+                .map(|key| CookieMessageStore::builder(key).build()),
+        }
+    }
+}
+
+/// 새 key로 서명하고, 검증에 실패하면 이전 key로 한 번 더 확인하는 flash message 저장소
+pub struct RotatingCookieMessageStore {
+    primary: CookieMessageStore,
+    fallback: Option<CookieMessageStore>,
+}
+
+impl FlashMessageStore for RotatingCookieMessageStore {
+    fn load(&self, request: &HttpRequest) -> Result<Vec<FlashMessage>, LoadError> {
+        match (self.primary.load(request), &self.fallback) {
+            (Err(e), Some(fallback)) => fallback.load(request).or(Err(e)),
+            (result, _) => result,
+        }
+    }
+
+    fn store(
+        &self,
+        messages: &[FlashMessage],
+        request: HttpRequest,
+        response: &mut ResponseHead,
+    ) -> Result<(), StoreError> {
+        self.primary.store(messages, request, response)
+    }
+}
+
+pub fn generate_key() -> String {
+    STANDARD.encode(Key::generate().master())
+}
+
+fn read_key(
+    key: &'static str,
+    value: Option<&str>,
+    file: Option<&Path>,
+) -> Result<Option<Key>, ConfigError> {
+    let encoded = match (value, file) {
+        (Some(value), _) => value.to_string(),
+        (None, Some(file)) => fs::read_to_string(file).map_err(|e| ConfigError::Read {
+            path: file.display().to_string(),
+            reason: e.to_string(),
+        })?,
+        (None, None) => return Ok(None),
+    };
+
+    let invalid = |reason: String| ConfigError::Invalid {
+        key,
+        value: "<redacted>".to_string(),
+        reason,
+    };
+
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| invalid(format!("must be base64 encoded: {e}")))?;
+    if bytes.len() < MIN_KEY_LEN {
+        return Err(invalid(format!(
+            "must decode to at least {MIN_KEY_LEN} bytes, got {}",
+            bytes.len()
+        )));
+    }
+
+    Ok(Some(Key::from(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_keys() {
+        let config = SecurityConfig {
+            signing_key: Some(generate_key()),
+            fallback_signing_key: Some(generate_key()),
+            ..SecurityConfig::default()
+        };
+        let keys = SigningKeys::load(&config, Environment::Production).unwrap();
+
+        assert_eq!(
+            STANDARD.encode(keys.primary.master()),
+            config.signing_key.unwrap()
+        );
+        assert!(keys.fallback.is_some());
+    }
+
+    #[test]
+    fn test_production_requires_key() {
+        let config = SecurityConfig::default();
+
+        assert!(matches!(
+            SigningKeys::load(&config, Environment::Production),
+            Err(ConfigError::Missing {
+                key: "security.signing_key"
+            })
+        ));
+        assert!(SigningKeys::load(&config, Environment::Development).is_ok());
+    }
+
+    #[test]
+    fn test_short_key_is_rejected() {
+        let config = SecurityConfig {
+            signing_key: Some(STANDARD.encode([0u8; 32])),
+            ..SecurityConfig::default()
+        };
+
+        assert!(matches!(
+            SigningKeys::load(&config, Environment::Development),
+            Err(ConfigError::Invalid {
+                key: "security.signing_key",
+                ..
+            })
+        ));
+    }
+}
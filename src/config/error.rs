@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("{key} must be set")]
+    Missing { key: &'static str },
+
+    #[error("{key} has an invalid value '{value}': {reason}")]
+    Invalid {
+        key: &'static str,
+        value: String,
+        reason: String,
+    },
+
+    #[error("failed to read config file {path}: {reason}")]
+    Read { path: String, reason: String },
+
+    #[error("failed to parse config file {path}: {reason}")]
+    Parse { path: String, reason: String },
+}
@@ -4,20 +4,39 @@ use actix_web::{http::header, HttpRequest};
 use serde_json::Value;
 use validator::ValidationErrors;
 
-const DEFAULT_LANGUAGE: &str = "en";
-const ALLOWED_LANGUAGES: &[&str] = &[DEFAULT_LANGUAGE, "ko", "de", "es", "fa", "pt", "ru", "tr"];
+pub const DEFAULT_LANGUAGE: &str = "en";
+pub const ALLOWED_LANGUAGES: &[&str] =
+    &[DEFAULT_LANGUAGE, "ko", "de", "es", "fa", "pt", "ru", "tr"];
 
 #[derive(Clone)]
 pub struct I18n {
     translations: HashMap<String, HashMap<String, String>>,
+    default_language: String,
 }
 
 impl I18n {
-    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
-        let translations = Self::load_all_translations(dir)
-            .unwrap_or_else(|e| panic!("translation load error: {e}"));
+    pub fn try_new<P: AsRef<Path>>(
+        dir: P,
+        default_language: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let translations = Self::load_all_translations(dir)?;
+
+        Ok(I18n {
+            translations,
+            default_language: default_language.to_string(),
+        })
+    }
+
+    pub fn missing_languages(&self) -> Vec<&'static str> {
+        ALLOWED_LANGUAGES
+            .iter()
+            .filter(|lang| !self.translations.contains_key(**lang))
+            .copied()
+            .collect()
+    }
 
-        I18n { translations }
+    pub fn language_count(&self) -> usize {
+        self.translations.len()
     }
 
     fn load_all_translations<P: AsRef<Path>>(
@@ -100,7 +119,8 @@ impl I18n {
             .get(header::ACCEPT_LANGUAGE)
             .and_then(|h| h.to_str().ok());
 
-        let preferred_language = Self::get_preferred_language(accept_language);
+        let preferred_language =
+            Self::get_preferred_language(accept_language, &self.default_language);
 
         self.translations
             .get(&preferred_language)
@@ -108,10 +128,10 @@ impl I18n {
             .unwrap_or_else(|| msg.to_string())
     }
 
-    fn get_preferred_language(header: Option<&str>) -> String {
+    fn get_preferred_language(header: Option<&str>, default_language: &str) -> String {
         let header = match header {
             Some(h) if !h.trim().is_empty() => h,
-            _ => return DEFAULT_LANGUAGE.to_string(),
+            _ => return default_language.to_string(),
         };
 
         let languages = Self::parse_accept_language(header);
@@ -121,7 +141,7 @@ impl I18n {
             }
         }
 
-        DEFAULT_LANGUAGE.to_string()
+        default_language.to_string()
     }
 
     fn parse_accept_language(header: &str) -> Vec<String> {
@@ -177,7 +197,7 @@ mod tests {
     fn test_none_header() {
         let none_header = None;
         assert_eq!(
-            I18n::get_preferred_language(none_header),
+            I18n::get_preferred_language(none_header, DEFAULT_LANGUAGE),
             DEFAULT_LANGUAGE.to_string()
         );
     }
@@ -186,13 +206,13 @@ mod tests {
     fn test_empty_header() {
         let empty_header = Some("");
         assert_eq!(
-            I18n::get_preferred_language(empty_header),
+            I18n::get_preferred_language(empty_header, DEFAULT_LANGUAGE),
             DEFAULT_LANGUAGE.to_string()
         );
 
         let space_header = Some("   ");
         assert_eq!(
-            I18n::get_preferred_language(space_header),
+            I18n::get_preferred_language(space_header, DEFAULT_LANGUAGE),
             DEFAULT_LANGUAGE.to_string()
         );
     }
@@ -201,7 +221,7 @@ mod tests {
     fn test_unsupported_language() {
         let unsupported_language = Some("fr");
         assert_eq!(
-            I18n::get_preferred_language(unsupported_language),
+            I18n::get_preferred_language(unsupported_language, DEFAULT_LANGUAGE),
             DEFAULT_LANGUAGE.to_string()
         );
     }
@@ -209,7 +229,10 @@ mod tests {
     #[test]
     fn test_multiple_languages() {
         let accept_language_header = Some("ko-KR,ko;q=0.9,en-US;q=0.8,en;q=0.7");
-        assert_eq!(I18n::get_preferred_language(accept_language_header), "ko");
+        assert_eq!(
+            I18n::get_preferred_language(accept_language_header, DEFAULT_LANGUAGE),
+            "ko"
+        );
     }
 
     #[test]
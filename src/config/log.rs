@@ -1,3 +1,183 @@
-pub fn init() {
-    tracing_subscriber::fmt().init();
+use std::{str::FromStr, sync::OnceLock, time::SystemTime};
+
+use opentelemetry::{
+    global,
+    trace::{Span as _, SpanKind, Status, Tracer as _, TracerProvider as _},
+    KeyValue,
+};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
+    trace::{SdkTracer, SdkTracerProvider},
+    Resource,
+};
+use sea_orm::{metric::Info, DbBackend};
+use serde::Deserialize;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+const DEFAULT_FILTER: &str = "info";
+const DEFAULT_SERVICE_NAME: &str = "actix-petclinic";
+
+/// OTLP exporter가 설정되었을 때만 채워지며, 비어 있으면 SQL 문장 span을 만들지 않음
+static STATEMENT_TRACER: OnceLock<SdkTracer> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 한 줄짜리 사람이 읽기 쉬운 형식
+    #[default]
+    Text,
+    /// 여러 줄로 field를 펼쳐서 보여주는 개발용 형식
+    Pretty,
+    /// 수집기가 읽을 수 있도록 한 줄에 JSON 하나씩 출력
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err("must be text, pretty or json".to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// OTLP/HTTP trace endpoint, 예: `http://localhost:4318/v1/traces`. 없으면 span을 내보내지 않음
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            format: LogFormat::default(),
+            otlp_endpoint: None,
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+        }
+    }
+}
+
+/// 종료될 때 아직 보내지 못한 span을 flush 하기 위해 main이 끝날 때까지 들고 있어야 함
+pub struct LogGuard {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("failed to flush traces: {e}");
+            }
+        }
+    }
+}
+
+/// level은 RUST_LOG로 조절하며, 명령 출력과 섞이지 않도록 log는 stderr로 보냄
+pub fn init(config: &LogConfig) -> LogGuard {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let fmt_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    let fmt_layer = match config.format {
+        LogFormat::Text => fmt_layer.boxed(),
+        LogFormat::Pretty => fmt_layer.pretty().boxed(),
+        LogFormat::Json => fmt_layer
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+    };
+
+    // subscriber가 설치되기 전이므로 exporter 생성 실패는 stderr로 직접 알림
+    let tracer_provider =
+        config.otlp_endpoint.as_deref().and_then(|endpoint| {
+            match tracer_provider(endpoint, &config.service_name) {
+                Ok(provider) => Some(provider),
+                Err(e) => {
+                    eprintln!("failed to create OTLP exporter for {endpoint}: {e}");
+                    None
+                }
+            }
+        });
+    let otel_layer = tracer_provider.as_ref().map(|provider| {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let _ = STATEMENT_TRACER.set(provider.tracer(DEFAULT_SERVICE_NAME));
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(DEFAULT_SERVICE_NAME))
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+
+    LogGuard { tracer_provider }
+}
+
+/// SQL 문장 하나마다 현재 tracing span의 자식으로 client span을 남김.
+/// sea-orm metric callback은 문장이 끝난 뒤에 불리므로 걸린 시간만큼 시작 시각을 거슬러 올림
+pub fn record_statement(info: &Info<'_>) {
+    let Some(tracer) = STATEMENT_TRACER.get() else {
+        return;
+    };
+
+    let end_time = SystemTime::now();
+    let start_time = end_time.checked_sub(info.elapsed).unwrap_or(end_time);
+    let system = match info.statement.db_backend {
+        DbBackend::MySql => "mysql",
+        DbBackend::Postgres => "postgresql",
+        DbBackend::Sqlite => "sqlite",
+    };
+    // bind 값은 placeholder로 남아 있으므로 SQL 문자열만 기록함
+    let operation = info
+        .statement
+        .sql
+        .split_whitespace()
+        .next()
+        .unwrap_or(system)
+        .to_ascii_uppercase();
+
+    let mut span = tracer
+        .span_builder(operation.clone())
+        .with_kind(SpanKind::Client)
+        .with_start_time(start_time)
+        .with_attributes([
+            KeyValue::new("db.system", system),
+            KeyValue::new("db.operation", operation),
+            KeyValue::new("db.statement", info.statement.sql.clone()),
+        ])
+        .start_with_context(tracer, &Span::current().context());
+    if info.failed {
+        span.set_status(Status::error("statement failed"));
+    }
+    span.end_with_timestamp(end_time);
+}
+
+fn tracer_provider(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<SdkTracerProvider, opentelemetry_otlp::ExporterBuildError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build())
 }
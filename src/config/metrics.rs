@@ -0,0 +1,180 @@
+use std::{sync::LazyLock, time::Instant};
+
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::Next,
+    Error,
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use sea_orm::{metric::Info, ConnectionTrait, DbBackend, DbConn};
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    db_query_duration: HistogramVec,
+    db_pool_connections: IntGaugeVec,
+    pub owners_created: IntCounter,
+    pub visits_booked: IntCounter,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("petclinic".to_string()), None)
+            .expect("metric prefix is valid");
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests by route pattern"),
+            &["method", "route", "status"],
+        )
+        .expect("metric definition is valid");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency by route pattern",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("metric definition is valid");
+        let db_query_duration = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "Database statement latency").buckets(
+                vec![
+                    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+                ],
+            ),
+            &["operation", "outcome"],
+        )
+        .expect("metric definition is valid");
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database pool connections by state"),
+            &["state"],
+        )
+        .expect("metric definition is valid");
+        let owners_created = IntCounter::new("owners_created_total", "Owners registered")
+            .expect("metric definition is valid");
+        let visits_booked = IntCounter::new("visits_booked_total", "Visits booked")
+            .expect("metric definition is valid");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(db_query_duration.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(owners_created.clone()),
+            Box::new(visits_booked.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Metrics {
+            registry,
+            http_requests,
+            http_request_duration,
+            db_query_duration,
+            db_pool_connections,
+            owners_created,
+            visits_booked,
+        }
+    }
+
+    /// sea-orm이 statement를 실행할 때마다 호출하는 callback. service 계층의 모든 query가 여기로 모임
+    pub fn observe_query(&self, info: &Info<'_>) {
+        let operation = info
+            .statement
+            .sql
+            .split_whitespace()
+            .next()
+            .unwrap_or("unknown")
+            .to_ascii_lowercase();
+        let outcome = if info.failed { "error" } else { "ok" };
+
+        self.db_query_duration
+            .with_label_values(&[operation.as_str(), outcome])
+            .observe(info.elapsed.as_secs_f64());
+    }
+
+    /// scrape 시점의 pool 상태를 기록하고 text format으로 내보냄
+    pub fn render(&self, conn: &DbConn) -> Result<String, prometheus::Error> {
+        self.record_pool(conn);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    fn record_pool(&self, conn: &DbConn) {
+        let (size, idle, max) = match conn.get_database_backend() {
+            DbBackend::MySql => {
+                let pool = conn.get_mysql_connection_pool();
+                (
+                    pool.size(),
+                    pool.num_idle(),
+                    pool.options().get_max_connections(),
+                )
+            }
+            DbBackend::Postgres => {
+                let pool = conn.get_postgres_connection_pool();
+                (
+                    pool.size(),
+                    pool.num_idle(),
+                    pool.options().get_max_connections(),
+                )
+            }
+            DbBackend::Sqlite => {
+                let pool = conn.get_sqlite_connection_pool();
+                (
+                    pool.size(),
+                    pool.num_idle(),
+                    pool.options().get_max_connections(),
+                )
+            }
+        };
+        let idle = idle as i64;
+        let size = i64::from(size);
+
+        for (state, value) in [
+            ("idle", idle),
+            ("in_use", size - idle),
+            ("max", i64::from(max)),
+        ] {
+            self.db_pool_connections
+                .with_label_values(&[state])
+                .set(value);
+        }
+    }
+}
+
+pub async fn track_request(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let started = Instant::now();
+    let method = req.method().to_string();
+
+    let res = next.call(req).await?;
+
+    // path 대신 route pattern을 label로 써서 id마다 series가 늘어나지 않도록 함
+    let route = res
+        .request()
+        .match_pattern()
+        .unwrap_or_else(|| "unmatched".to_string());
+    let status = res.status().as_u16().to_string();
+    let labels = [method.as_str(), route.as_str(), status.as_str()];
+
+    METRICS.http_requests.with_label_values(&labels).inc();
+    METRICS
+        .http_request_duration
+        .with_label_values(&labels)
+        .observe(started.elapsed().as_secs_f64());
+
+    Ok(res)
+}
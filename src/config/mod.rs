@@ -1,6 +1,11 @@
+pub mod app;
+pub mod cookie;
 pub mod db;
 pub mod env;
+pub mod error;
 pub mod i18n;
 pub mod log;
+pub mod metrics;
+pub mod request_id;
 pub mod server;
 pub mod tera;
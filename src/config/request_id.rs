@@ -0,0 +1,145 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    middleware::{Logger, Next},
+    Error, HttpMessage,
+};
+use opentelemetry::{global, propagation::Extractor};
+use tracing::{field, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+const MAX_INCOMING_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+#[derive(Clone)]
+struct RequestId(String);
+
+/// 현재 처리 중인 요청의 id. 요청 밖(CLI 등)에서는 None
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// access log는 body 전송이 끝난 뒤 span 밖에서 찍히므로 request extension에서 id를 꺼내 씀
+pub fn access_log() -> Logger {
+    Logger::new(r#"%a "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T request_id=%{request_id}xi"#)
+        .custom_request_replace("request_id", |req| {
+            req.extensions()
+                .get::<RequestId>()
+                .map(|RequestId(id)| id.clone())
+                .unwrap_or_else(|| "-".to_string())
+        })
+}
+
+pub async fn assign_request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    // proxy가 이미 붙인 id가 있으면 이어서 쓰고, 없거나 이상하면 새로 만듦
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.path(),
+        otel.name = field::Empty,
+        otel.kind = "server",
+        http.route = field::Empty,
+        http.response.status_code = field::Empty,
+    );
+    // 앞단 서비스가 보낸 traceparent가 있으면 같은 trace로 이어 붙임
+    let parent = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(req.headers()))
+    });
+    let _ = span.set_parent(parent);
+
+    let method = req.method().to_string();
+    let mut res = REQUEST_ID
+        .scope(request_id.clone(), next.call(req).instrument(span.clone()))
+        .await?;
+
+    let route = res
+        .request()
+        .match_pattern()
+        .unwrap_or_else(|| "unmatched".to_string());
+    span.record("otel.name", format!("{method} {route}"));
+    span.record("http.route", route);
+    span.record("http.response.status_code", res.status().as_u16());
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    Ok(res)
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+fn is_valid(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_INCOMING_LEN
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{middleware, test, web, App, HttpResponse};
+
+    use super::*;
+
+    #[actix_web::test]
+    async fn test_request_id_is_echoed() {
+        let app = test::init_service(
+            App::new()
+                .wrap(middleware::from_fn(assign_request_id))
+                .route(
+                    "/",
+                    web::get().to(|| async { HttpResponse::Ok().body(current().unwrap()) }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((REQUEST_ID_HEADER, "abc-123"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(REQUEST_ID_HEADER).unwrap(), "abc-123");
+        assert_eq!(test::read_body(res).await, "abc-123");
+
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((REQUEST_ID_HEADER, "bad id"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let generated = res.headers().get(REQUEST_ID_HEADER).unwrap();
+        assert!(Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+    }
+}
@@ -1,37 +1,71 @@
+use super::{app::ServerConfig, cookie::SigningKeys, metrics, request_id};
 use crate::{model::error_response::ErrorResponse, web, AppState};
 use actix_files::Files;
+use actix_multipart::{form::MultipartFormConfig, MultipartError};
 use actix_web::{
-    cookie::Key,
     dev::ServiceResponse,
+    error::{ErrorPayloadTooLarge, PayloadError},
+    http::header,
     middleware::{self, ErrorHandlerResponse, ErrorHandlers},
     web::Data,
     App, Error, HttpResponse, HttpServer, Result,
 };
-use actix_web_flash_messages::{storage::CookieMessageStore, FlashMessagesFramework};
+use actix_web_flash_messages::FlashMessagesFramework;
 
-pub async fn start_server(app_state: AppState) -> std::io::Result<()> {
-    let signing_key = Key::generate();
-    let message_store = CookieMessageStore::builder(signing_key).build();
-    let message_framework = FlashMessagesFramework::builder(message_store).build();
+pub async fn start_server(app_state: AppState, signing_keys: SigningKeys) -> std::io::Result<()> {
+    let ServerConfig {
+        bind_address,
+        workers,
+        ..
+    } = app_state.config.server.clone();
+    let static_dir = app_state.config.paths.static_files.clone();
+    // 사진이나 첨부 파일보다 조금 큰 요청까지는 읽어서 form에 크기 오류를 보여주고,
+    // 그보다 크면 읽는 도중 거절함
+    let multipart_limit = app_state
+        .config
+        .photos
+        .max_bytes
+        .max(app_state.config.attachments.max_bytes)
+        .saturating_add(1024 * 1024);
+    let multipart_config = MultipartFormConfig::default()
+        .total_limit(multipart_limit)
+        .memory_limit(multipart_limit)
+        .error_handler(|err, _| match err {
+            MultipartError::Payload(PayloadError::Overflow) => ErrorPayloadTooLarge(err),
+            _ => err.into(),
+        });
 
-    HttpServer::new(move || {
+    let message_framework = FlashMessagesFramework::builder(signing_keys.message_store()).build();
+
+    let mut server = HttpServer::new(move || {
         App::new()
             .app_data(Data::new(app_state.clone()))
-            .wrap(middleware::Logger::default())
+            .app_data(multipart_config.clone())
+            .wrap(request_id::access_log())
+            .wrap(middleware::from_fn(metrics::track_request))
             .wrap(middleware::NormalizePath::trim())
             .wrap(middleware::Compress::default())
             .wrap(message_framework.clone())
             .wrap(ErrorHandlers::new().default_handler(error_handler))
-            .service(Files::new("/static", "./static").show_files_listing())
+            .wrap(middleware::from_fn(request_id::assign_request_id))
+            .service(Files::new("/static", &static_dir).show_files_listing())
             .configure(web::configure_route)
-    })
-    .bind("127.0.0.1:8080")?
-    .run()
-    .await
+    });
+    if let Some(workers) = workers {
+        server = server.workers(workers);
+    }
+
+    server.bind(bind_address)?.run().await
 }
 
 fn error_handler<B>(res: ServiceResponse<B>) -> Result<ErrorHandlerResponse<B>> {
-    if res.response().headers().contains_key("App-Error") {
+    // AppError나 health check처럼 handler가 이미 JSON 본문을 만든 응답은 그대로 둠
+    let is_json = res
+        .response()
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == "application/json");
+    if res.response().headers().contains_key("App-Error") || is_json {
         return Ok(ErrorHandlerResponse::Response(res.map_into_left_body()));
     }
 
@@ -1,5 +1,21 @@
+use std::error::Error;
+
 use tera::Tera;
 
-pub fn init() -> Tera {
-    Tera::new("templates/**/*.html").expect("Tera parsing error")
+use super::app::PathConfig;
+
+pub fn try_init(paths: &PathConfig) -> tera::Result<Tera> {
+    Tera::new(&paths.template_glob())
+}
+
+/// tera 오류는 실제 원인이 source에 있으므로 원인까지 이어 붙여서 보여줌
+pub fn describe_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+
+    message
 }
@@ -0,0 +1,60 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "billable_items")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub visit_id: i32,
+    pub kind: BillableItemKind,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price_cents: i64,
+    /// 아직 청구하지 않은 항목은 None임
+    pub invoice_id: Option<i32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+#[serde(rename_all = "lowercase")]
+pub enum BillableItemKind {
+    #[sea_orm(string_value = "service")]
+    Service,
+    #[sea_orm(string_value = "medication")]
+    Medication,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::visit::Entity",
+        from = "Column::VisitId",
+        to = "super::visit::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Visits,
+    #[sea_orm(
+        belongs_to = "super::invoice::Entity",
+        from = "Column::InvoiceId",
+        to = "super::invoice::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Invoices,
+}
+
+impl Related<super::visit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Visits.def()
+    }
+}
+
+impl Related<super::invoice::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invoices.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "invoices")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub owner_id: i32,
+    pub issued_on: Date,
+    pub subtotal_cents: i64,
+    /// 발행 시점의 세율. 1 basis point는 0.01%임
+    pub tax_rate_basis_points: i32,
+    pub tax_cents: i64,
+    pub total_cents: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::owners::Entity",
+        from = "Column::OwnerId",
+        to = "super::owners::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Owners,
+    #[sea_orm(has_many = "super::billable_item::Entity")]
+    BillableItems,
+    #[sea_orm(has_many = "super::payment::Entity")]
+    Payments,
+}
+
+impl Related<super::owners::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owners.def()
+    }
+}
+
+impl Related<super::billable_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BillableItems.def()
+    }
+}
+
+impl Related<super::payment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Payments.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
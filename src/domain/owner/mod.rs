@@ -1,4 +1,12 @@
+pub mod billable_item;
+pub mod invoice;
+pub mod owner_phone;
 pub mod owners;
+pub mod payment;
 pub mod pet;
+pub mod pet_transfer;
+pub mod prescription;
 pub mod types;
+pub mod vaccination;
 pub mod visit;
+pub mod visit_attachment;
@@ -0,0 +1,45 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "owner_phones")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub owner_id: i32,
+    pub kind: PhoneKind,
+    /// E.164 형식("+16085551023")
+    pub number: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+#[serde(rename_all = "lowercase")]
+pub enum PhoneKind {
+    #[sea_orm(string_value = "mobile")]
+    Mobile,
+    #[sea_orm(string_value = "home")]
+    Home,
+    #[sea_orm(string_value = "work")]
+    Work,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::owners::Entity",
+        from = "Column::OwnerId",
+        to = "super::owners::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Owners,
+}
+
+impl Related<super::owners::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Owners.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,22 +1,45 @@
 use sea_orm::entity::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "owners")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub id: u32,
+    pub id: i32,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub address: Option<String>,
     pub city: Option<String>,
-    pub telephone: Option<String>,
+    pub email: Option<String>,
+    pub preferred_contact: ContactMethod,
+    pub emergency_contact_name: Option<String>,
+    /// E.164 형식
+    pub emergency_contact_phone: Option<String>,
+}
+
+/// owner가 연락받기를 원하는 방법
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+#[serde(rename_all = "lowercase")]
+pub enum ContactMethod {
+    #[sea_orm(string_value = "phone")]
+    Phone,
+    #[sea_orm(string_value = "sms")]
+    Sms,
+    #[sea_orm(string_value = "email")]
+    Email,
+    #[sea_orm(string_value = "post")]
+    Post,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::pet::Entity")]
     Pets,
+    #[sea_orm(has_many = "super::invoice::Entity")]
+    Invoices,
+    #[sea_orm(has_many = "super::owner_phone::Entity")]
+    Phones,
 }
 
 impl Related<super::pet::Entity> for Entity {
@@ -25,4 +48,16 @@ impl Related<super::pet::Entity> for Entity {
     }
 }
 
+impl Related<super::invoice::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invoices.def()
+    }
+}
+
+impl Related<super::owner_phone::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Phones.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -5,11 +5,14 @@ use serde::Serialize;
 #[sea_orm(table_name = "pets")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub id: u32,
+    pub id: i32,
     pub name: Option<String>,
     pub birth_date: Option<Date>,
-    pub type_id: u32,
-    pub owner_id: Option<u32>,
+    pub type_id: i32,
+    pub owner_id: Option<i32>,
+    /// 저장소에 있는 사진과 thumbnail의 key. 사진이 없으면 둘 다 None임
+    pub photo_key: Option<String>,
+    pub thumbnail_key: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +35,10 @@ pub enum Relation {
     Types,
     #[sea_orm(has_many = "super::visit::Entity")]
     Visits,
+    #[sea_orm(has_many = "super::pet_transfer::Entity")]
+    PetTransfers,
+    #[sea_orm(has_many = "super::vaccination::Entity")]
+    Vaccinations,
 }
 
 impl Related<super::owners::Entity> for Entity {
@@ -52,4 +59,16 @@ impl Related<super::visit::Entity> for Entity {
     }
 }
 
+impl Related<super::pet_transfer::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PetTransfers.def()
+    }
+}
+
+impl Related<super::vaccination::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vaccinations.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "pet_transfers")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub pet_id: i32,
+    pub from_owner_id: Option<i32>,
+    pub to_owner_id: Option<i32>,
+    pub transferred_on: Date,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Pets,
+    #[sea_orm(
+        belongs_to = "super::owners::Entity",
+        from = "Column::FromOwnerId",
+        to = "super::owners::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    FromOwner,
+    #[sea_orm(
+        belongs_to = "super::owners::Entity",
+        from = "Column::ToOwnerId",
+        to = "super::owners::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    ToOwner,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
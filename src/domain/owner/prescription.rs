@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "prescriptions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub visit_id: i32,
+    pub kind: PrescriptionKind,
+    pub drug: String,
+    pub dose: String,
+    pub frequency: String,
+    pub duration_days: i32,
+    /// 처방한 vet. visit의 담당 vet과 다를 수 있음
+    pub vet_id: i32,
+}
+
+/// 진료 중에 한 처치(treatment)와 집에서 투약할 처방(prescription)을 구분함
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+#[serde(rename_all = "lowercase")]
+pub enum PrescriptionKind {
+    #[sea_orm(string_value = "treatment")]
+    Treatment,
+    #[sea_orm(string_value = "prescription")]
+    Prescription,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::visit::Entity",
+        from = "Column::VisitId",
+        to = "super::visit::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Visits,
+    #[sea_orm(
+        belongs_to = "crate::domain::veterinarian::vet::Entity",
+        from = "Column::VetId",
+        to = "crate::domain::veterinarian::vet::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Vets,
+}
+
+impl Related<super::visit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Visits.def()
+    }
+}
+
+impl Related<crate::domain::veterinarian::vet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
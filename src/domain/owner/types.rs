@@ -5,7 +5,7 @@ use serde::Serialize;
 #[sea_orm(table_name = "types")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub id: u32,
+    pub id: i32,
     pub name: Option<String>,
 }
 
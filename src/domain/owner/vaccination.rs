@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "vaccinations")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub pet_id: i32,
+    pub vaccine: String,
+    pub given_on: Date,
+    pub lot_number: Option<String>,
+    pub vet_id: Option<i32>,
+    pub next_due_on: Option<Date>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::pet::Entity",
+        from = "Column::PetId",
+        to = "super::pet::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Pets,
+    #[sea_orm(
+        belongs_to = "crate::domain::veterinarian::vet::Entity",
+        from = "Column::VetId",
+        to = "crate::domain::veterinarian::vet::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Vets,
+}
+
+impl Related<super::pet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Pets.def()
+    }
+}
+
+impl Related<crate::domain::veterinarian::vet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vets.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,13 +1,29 @@
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
 #[sea_orm(table_name = "visits")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub id: u32,
-    pub pet_id: Option<u32>,
+    pub id: i32,
+    pub pet_id: Option<i32>,
     pub visit_date: Option<Date>,
     pub description: Option<String>,
+    pub status: VisitStatus,
+    pub vet_id: Option<i32>,
+    pub starts_at: Option<DateTime>,
+    pub duration_minutes: Option<i32>,
+    pub room: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
+#[serde(rename_all = "lowercase")]
+pub enum VisitStatus {
+    #[sea_orm(string_value = "scheduled")]
+    Scheduled,
+    #[sea_orm(string_value = "cancelled")]
+    Cancelled,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -20,6 +36,20 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Pets,
+    #[sea_orm(
+        belongs_to = "crate::domain::veterinarian::vet::Entity",
+        from = "Column::VetId",
+        to = "crate::domain::veterinarian::vet::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Vets,
+    #[sea_orm(has_many = "super::billable_item::Entity")]
+    BillableItems,
+    #[sea_orm(has_many = "super::prescription::Entity")]
+    Prescriptions,
+    #[sea_orm(has_many = "super::visit_attachment::Entity")]
+    Attachments,
 }
 
 impl Related<super::pet::Entity> for Entity {
@@ -28,4 +58,28 @@ impl Related<super::pet::Entity> for Entity {
     }
 }
 
+impl Related<crate::domain::veterinarian::vet::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vets.def()
+    }
+}
+
+impl Related<super::billable_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::BillableItems.def()
+    }
+}
+
+impl Related<super::prescription::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Prescriptions.def()
+    }
+}
+
+impl Related<super::visit_attachment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Attachments.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "visit_attachments")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub visit_id: i32,
+    /// 업로드할 때의 파일 이름. 내려받을 때 이 이름을 씀
+    pub file_name: String,
+    /// 업로드 요청의 값이 아니라 파일 내용으로 알아낸 형식
+    pub content_type: String,
+    pub size_bytes: i64,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    pub uploaded_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::visit::Entity",
+        from = "Column::VisitId",
+        to = "super::visit::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Visits,
+}
+
+impl Related<super::visit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Visits.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
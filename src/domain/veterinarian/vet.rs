@@ -5,15 +5,18 @@ use serde::Serialize;
 #[sea_orm(table_name = "vets")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub id: u32,
+    pub id: i32,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
+    pub active: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::vet_specialty::Entity")]
     VetSpecialties,
+    #[sea_orm(has_many = "crate::domain::owner::visit::Entity")]
+    Visits,
 }
 
 impl Related<super::vet_specialty::Entity> for Entity {
@@ -22,4 +25,10 @@ impl Related<super::vet_specialty::Entity> for Entity {
     }
 }
 
+impl Related<crate::domain::owner::visit::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Visits.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
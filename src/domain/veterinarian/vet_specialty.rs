@@ -4,9 +4,9 @@ use sea_orm::entity::prelude::*;
 #[sea_orm(table_name = "vet_specialties")]
 pub struct Model {
     #[sea_orm(primary_key)]
-    pub vet_id: u32,
+    pub vet_id: i32,
     #[sea_orm(primary_key)]
-    pub specialty_id: u32,
+    pub specialty_id: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -1,11 +1,19 @@
-use config::i18n::I18n;
+use std::{io, process::ExitCode, sync::Arc};
+
+use clap::Parser;
+use cli::{Cli, Command};
+use config::{app::AppConfig, cookie::SigningKeys, error::ConfigError, i18n::I18n, log::LogConfig};
 use sea_orm::DbConn;
+use storage::Storage;
 use tera::Tera;
 
+mod cli;
 mod config;
 mod domain;
+mod migration;
 mod model;
 mod service;
+mod storage;
 mod web;
 
 #[derive(Clone)]
@@ -13,18 +21,93 @@ struct AppState {
     conn: DbConn,
     tera: Tera,
     i18n: I18n,
+    config: AppConfig,
+    storage: Arc<dyn Storage>,
 }
 
 #[actix_web::main]
-async fn main() -> std::io::Result<()> {
+async fn main() -> ExitCode {
     config::env::load();
-    config::log::init();
 
-    let i18n = config::i18n::I18n::new("locales");
-    let tera = config::tera::init();
-    let conn = config::db::connect_db().await;
+    let Cli {
+        config: config_path,
+        command,
+    } = Cli::parse();
+    // log 형식도 설정에 들어 있으므로 설정을 먼저 읽고, 실패하면 기본 형식으로 log를 시작함
+    let app_config = AppConfig::load(config_path.as_deref());
+    let _log_guard = config::log::init(
+        app_config
+            .as_ref()
+            .map(|config| &config.log)
+            .unwrap_or(&LogConfig::default()),
+    );
+
+    let result = run(command.unwrap_or(Command::Serve), app_config).await;
+
+    // panic이나 Debug 출력 대신 원인을 한 줄로 보여줌
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+async fn run(command: Command, app_config: Result<AppConfig, ConfigError>) -> io::Result<()> {
+    let with_paths = |app_config: Result<AppConfig, ConfigError>| {
+        app_config.and_then(|config| config.validate_paths().map(|()| config))
+    };
+
+    match command {
+        Command::Serve => serve(with_paths(app_config).map_err(io::Error::other)?).await,
+        Command::Migrate(args) => {
+            cli::migrate::run(&app_config.map_err(io::Error::other)?, args).await
+        }
+        Command::Seed(args) => cli::seed::run(&app_config.map_err(io::Error::other)?, args).await,
+        Command::CheckConfig => cli::check_config::run(with_paths(app_config)).await,
+        Command::Routes => {
+            cli::routes::run();
+            Ok(())
+        }
+        Command::GenerateKey => {
+            println!("{}", config::cookie::generate_key());
+            Ok(())
+        }
+    }
+}
+
+async fn serve(app_config: AppConfig) -> io::Result<()> {
+    let signing_keys = SigningKeys::load(&app_config.security, app_config.server.environment)
+        .map_err(io::Error::other)?;
+
+    let i18n = I18n::try_new(&app_config.paths.locales, &app_config.i18n.default_language)
+        .map_err(|e| {
+            io::Error::other(format!(
+                "failed to load translations from {}: {e}",
+                app_config.paths.locales.display()
+            ))
+        })?;
+    let tera = config::tera::try_init(&app_config.paths).map_err(|e| {
+        io::Error::other(format!(
+            "failed to load templates: {}",
+            config::tera::describe_error(&e)
+        ))
+    })?;
+    let conn = config::db::connect_db(&app_config.database)
+        .await
+        .map_err(io::Error::other)?;
+    if app_config.database.auto_migrate {
+        config::db::migrate(&conn).await.map_err(io::Error::other)?;
+    }
+    let storage = storage::init(&app_config.storage)?;
 
-    let app_state = AppState { conn, tera, i18n };
+    let app_state = AppState {
+        conn,
+        tera,
+        i18n,
+        config: app_config,
+        storage,
+    };
 
-    config::server::start_server(app_state).await
+    config::server::start_server(app_state, signing_keys).await
 }
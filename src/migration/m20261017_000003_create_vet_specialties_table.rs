@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261017_000001_create_vets_table::Vets,
+    m20261017_000002_create_specialties_table::Specialties,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VetSpecialties::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VetSpecialties::VetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(VetSpecialties::SpecialtyId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(VetSpecialties::VetId)
+                            .col(VetSpecialties::SpecialtyId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VetSpecialties::Table, VetSpecialties::VetId)
+                            .to(Vets::Table, Vets::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VetSpecialties::Table, VetSpecialties::SpecialtyId)
+                            .to(Specialties::Table, Specialties::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VetSpecialties::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum VetSpecialties {
+    Table,
+    VetId,
+    SpecialtyId,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Owners::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Owners::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Owners::FirstName).string_len(30))
+                    .col(ColumnDef::new(Owners::LastName).string_len(30))
+                    .col(ColumnDef::new(Owners::Address).string_len(255))
+                    .col(ColumnDef::new(Owners::City).string_len(80))
+                    .col(ColumnDef::new(Owners::Telephone).string_len(20))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_owners_last_name")
+                    .table(Owners::Table)
+                    .col(Owners::LastName)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Owners::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Owners {
+    Table,
+    Id,
+    FirstName,
+    LastName,
+    Address,
+    City,
+    Telephone,
+}
@@ -0,0 +1,70 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261017_000004_create_types_table::Types, m20261017_000005_create_owners_table::Owners,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Pets::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Pets::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Pets::Name).string_len(30))
+                    .col(ColumnDef::new(Pets::BirthDate).date())
+                    .col(ColumnDef::new(Pets::TypeId).integer().not_null())
+                    .col(ColumnDef::new(Pets::OwnerId).integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Pets::Table, Pets::OwnerId)
+                            .to(Owners::Table, Owners::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Pets::Table, Pets::TypeId)
+                            .to(Types::Table, Types::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pets_name")
+                    .table(Pets::Table)
+                    .col(Pets::Name)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Pets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Pets {
+    Table,
+    Id,
+    Name,
+    BirthDate,
+    TypeId,
+    OwnerId,
+}
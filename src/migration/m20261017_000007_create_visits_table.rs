@@ -0,0 +1,50 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000006_create_pets_table::Pets;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Visits::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Visits::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Visits::PetId).integer())
+                    .col(ColumnDef::new(Visits::VisitDate).date())
+                    .col(ColumnDef::new(Visits::Description).string_len(255))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Visits::Table, Visits::PetId)
+                            .to(Pets::Table, Pets::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Visits::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Visits {
+    Table,
+    Id,
+    PetId,
+    VisitDate,
+    Description,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000007_create_visits_table::Visits;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 취소한 visit도 기록으로 남기기 위해 삭제 대신 status를 바꿈
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Visits::Table)
+                    .add_column(
+                        ColumnDef::new(VisitStatus::Status)
+                            .string_len(20)
+                            .not_null()
+                            .default("scheduled"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Visits::Table)
+                    .drop_column(VisitStatus::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VisitStatus {
+    Status,
+}
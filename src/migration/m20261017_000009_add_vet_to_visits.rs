@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+use super::{
+    m20261017_000001_create_vets_table::Vets, m20261017_000007_create_visits_table::Visits,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 기존 visit에는 담당 vet 기록이 없으므로 nullable로 추가함
+        let mut vet_id = ColumnDef::new(VisitVet::VetId);
+        vet_id.integer();
+
+        let mut alter = Table::alter();
+        alter.table(Visits::Table);
+
+        // SQLite는 ALTER TABLE로 foreign key를 추가할 수 없어 column 정의에 REFERENCES를 붙임
+        if manager.get_database_backend() == DbBackend::Sqlite {
+            vet_id.extra("REFERENCES vets (id)");
+            alter.add_column(&mut vet_id);
+        } else {
+            alter.add_column(&mut vet_id).add_foreign_key(
+                TableForeignKey::new()
+                    .name("fk_visits_vet_id")
+                    .from_tbl(Visits::Table)
+                    .from_col(VisitVet::VetId)
+                    .to_tbl(Vets::Table)
+                    .to_col(Vets::Id),
+            );
+        }
+
+        manager.alter_table(alter.to_owned()).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_visits_vet_id")
+                    .table(Visits::Table)
+                    .col(VisitVet::VetId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_visits_vet_id")
+                    .table(Visits::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut alter = Table::alter();
+        alter.table(Visits::Table);
+        if manager.get_database_backend() != DbBackend::Sqlite {
+            alter.drop_foreign_key(Alias::new("fk_visits_vet_id"));
+        }
+        alter.drop_column(VisitVet::VetId);
+
+        manager.alter_table(alter.to_owned()).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VisitVet {
+    VetId,
+}
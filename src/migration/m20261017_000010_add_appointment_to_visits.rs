@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000007_create_visits_table::Visits;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite는 ALTER TABLE 한 번에 column 하나만 추가할 수 있어 나눠서 실행함
+        for mut column in [
+            ColumnDef::new(Appointment::StartsAt).date_time().to_owned(),
+            ColumnDef::new(Appointment::DurationMinutes)
+                .integer()
+                .to_owned(),
+            ColumnDef::new(Appointment::Room).string_len(30).to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Visits::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_visits_starts_at")
+                    .table(Visits::Table)
+                    .col(Appointment::StartsAt)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_visits_starts_at")
+                    .table(Visits::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            Appointment::StartsAt,
+            Appointment::DurationMinutes,
+            Appointment::Room,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Visits::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Appointment {
+    StartsAt,
+    DurationMinutes,
+    Room,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000001_create_vets_table::Vets;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 은퇴한 vet도 지난 visit 기록에 남아 있어야 하므로 삭제 대신 active를 끔
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Vets::Table)
+                    .add_column(
+                        ColumnDef::new(VetActive::Active)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Vets::Table)
+                    .drop_column(VetActive::Active)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum VetActive {
+    Active,
+}
@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000004_create_types_table::Types;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 같은 이름의 pet type이 동시에 추가되지 않도록 기존 index를 unique index로 바꿈
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_types_name")
+                    .table(Types::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_types_name")
+                    .table(Types::Table)
+                    .col(Types::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_types_name")
+                    .table(Types::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_types_name")
+                    .table(Types::Table)
+                    .col(Types::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+}
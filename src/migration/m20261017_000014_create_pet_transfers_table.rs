@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261017_000005_create_owners_table::Owners, m20261017_000006_create_pets_table::Pets,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // owner를 삭제해도 pet의 이전 기록은 남도록 owner 참조는 nullable로 둠
+        manager
+            .create_table(
+                Table::create()
+                    .table(PetTransfers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PetTransfers::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PetTransfers::PetId).integer().not_null())
+                    .col(ColumnDef::new(PetTransfers::FromOwnerId).integer())
+                    .col(ColumnDef::new(PetTransfers::ToOwnerId).integer())
+                    .col(
+                        ColumnDef::new(PetTransfers::TransferredOn)
+                            .date()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PetTransfers::Table, PetTransfers::PetId)
+                            .to(Pets::Table, Pets::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PetTransfers::Table, PetTransfers::FromOwnerId)
+                            .to(Owners::Table, Owners::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(PetTransfers::Table, PetTransfers::ToOwnerId)
+                            .to(Owners::Table, Owners::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_pet_transfers_pet_id")
+                    .table(PetTransfers::Table)
+                    .col(PetTransfers::PetId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PetTransfers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum PetTransfers {
+    Table,
+    Id,
+    PetId,
+    FromOwnerId,
+    ToOwnerId,
+    TransferredOn,
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+use super::{m20261017_000001_create_vets_table::Vets, m20261017_000006_create_pets_table::Pets};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Vaccinations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Vaccinations::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Vaccinations::PetId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Vaccinations::Vaccine)
+                            .string_len(80)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Vaccinations::GivenOn).date().not_null())
+                    .col(ColumnDef::new(Vaccinations::LotNumber).string_len(40))
+                    .col(ColumnDef::new(Vaccinations::VetId).integer())
+                    .col(ColumnDef::new(Vaccinations::NextDueOn).date())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Vaccinations::Table, Vaccinations::PetId)
+                            .to(Pets::Table, Pets::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Vaccinations::Table, Vaccinations::VetId)
+                            .to(Vets::Table, Vets::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_vaccinations_pet_id")
+                    .table(Vaccinations::Table)
+                    .col(Vaccinations::PetId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 접종 예정 보고서는 다음 접종일로 조회함
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_vaccinations_next_due_on")
+                    .table(Vaccinations::Table)
+                    .col(Vaccinations::NextDueOn)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Vaccinations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Vaccinations {
+    Table,
+    Id,
+    PetId,
+    Vaccine,
+    GivenOn,
+    LotNumber,
+    VetId,
+    NextDueOn,
+}
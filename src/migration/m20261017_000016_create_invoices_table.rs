@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000005_create_owners_table::Owners;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 금액은 모두 최소 화폐 단위(cent)의 정수로 저장함
+        manager
+            .create_table(
+                Table::create()
+                    .table(Invoices::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Invoices::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Invoices::OwnerId).integer().not_null())
+                    .col(ColumnDef::new(Invoices::IssuedOn).date().not_null())
+                    .col(
+                        ColumnDef::new(Invoices::SubtotalCents)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Invoices::TaxRateBasisPoints)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Invoices::TaxCents).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(Invoices::TotalCents)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Invoices::Table, Invoices::OwnerId)
+                            .to(Owners::Table, Owners::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_invoices_owner_id")
+                    .table(Invoices::Table)
+                    .col(Invoices::OwnerId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Invoices::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Invoices {
+    Table,
+    Id,
+    OwnerId,
+    IssuedOn,
+    SubtotalCents,
+    TaxRateBasisPoints,
+    TaxCents,
+    TotalCents,
+}
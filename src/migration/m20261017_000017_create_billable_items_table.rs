@@ -0,0 +1,98 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261017_000007_create_visits_table::Visits, m20261017_000016_create_invoices_table::Invoices,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // invoice_id가 null이면 아직 청구하지 않은 항목임
+        manager
+            .create_table(
+                Table::create()
+                    .table(BillableItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BillableItems::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(BillableItems::VisitId).integer().not_null())
+                    .col(
+                        ColumnDef::new(BillableItems::Kind)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BillableItems::Description)
+                            .string_len(120)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(BillableItems::Quantity).integer().not_null())
+                    .col(
+                        ColumnDef::new(BillableItems::UnitPriceCents)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(BillableItems::InvoiceId).integer())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(BillableItems::Table, BillableItems::VisitId)
+                            .to(Visits::Table, Visits::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(BillableItems::Table, BillableItems::InvoiceId)
+                            .to(Invoices::Table, Invoices::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_billable_items_visit_id")
+                    .table(BillableItems::Table)
+                    .col(BillableItems::VisitId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_billable_items_invoice_id")
+                    .table(BillableItems::Table)
+                    .col(BillableItems::InvoiceId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BillableItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum BillableItems {
+    Table,
+    Id,
+    VisitId,
+    Kind,
+    Description,
+    Quantity,
+    UnitPriceCents,
+    InvoiceId,
+}
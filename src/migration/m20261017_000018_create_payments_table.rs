@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000016_create_invoices_table::Invoices;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Payments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Payments::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Payments::InvoiceId).integer().not_null())
+                    .col(ColumnDef::new(Payments::PaidOn).date().not_null())
+                    .col(
+                        ColumnDef::new(Payments::AmountCents)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Payments::Method).string_len(20).not_null())
+                    .col(ColumnDef::new(Payments::Reference).string_len(80))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Payments::Table, Payments::InvoiceId)
+                            .to(Invoices::Table, Invoices::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payments_invoice_id")
+                    .table(Payments::Table)
+                    .col(Payments::InvoiceId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Payments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Payments {
+    Table,
+    Id,
+    InvoiceId,
+    PaidOn,
+    AmountCents,
+    Method,
+    Reference,
+}
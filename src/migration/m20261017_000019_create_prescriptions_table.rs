@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+use super::{
+    m20261017_000001_create_vets_table::Vets, m20261017_000007_create_visits_table::Visits,
+};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Prescriptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Prescriptions::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Prescriptions::VisitId).integer().not_null())
+                    .col(
+                        ColumnDef::new(Prescriptions::Kind)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Prescriptions::Drug)
+                            .string_len(80)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Prescriptions::Dose)
+                            .string_len(40)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Prescriptions::Frequency)
+                            .string_len(40)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Prescriptions::DurationDays)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Prescriptions::VetId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Prescriptions::Table, Prescriptions::VisitId)
+                            .to(Visits::Table, Visits::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(Prescriptions::Table, Prescriptions::VetId)
+                            .to(Vets::Table, Vets::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_prescriptions_visit_id")
+                    .table(Prescriptions::Table)
+                    .col(Prescriptions::VisitId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Prescriptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Prescriptions {
+    Table,
+    Id,
+    VisitId,
+    Kind,
+    Drug,
+    Dose,
+    Frequency,
+    DurationDays,
+    VetId,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000005_create_owners_table::Owners;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite는 ALTER TABLE 한 번에 column 하나만 추가할 수 있어 나눠서 실행함
+        for mut column in [
+            ColumnDef::new(ContactDetails::Email)
+                .string_len(120)
+                .to_owned(),
+            ColumnDef::new(ContactDetails::PreferredContact)
+                .string_len(20)
+                .not_null()
+                .default("phone")
+                .to_owned(),
+            ColumnDef::new(ContactDetails::EmergencyContactName)
+                .string_len(80)
+                .to_owned(),
+            ColumnDef::new(ContactDetails::EmergencyContactPhone)
+                .string_len(20)
+                .to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Owners::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            ContactDetails::Email,
+            ContactDetails::PreferredContact,
+            ContactDetails::EmergencyContactName,
+            ContactDetails::EmergencyContactPhone,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Owners::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ContactDetails {
+    Email,
+    PreferredContact,
+    EmergencyContactName,
+    EmergencyContactPhone,
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use sea_orm_migration::{prelude::*, sea_orm::ConnectionTrait};
+
+use super::m20261017_000005_create_owners_table::Owners;
+
+// 이전 owner form은 10자리 북미 번호만 받았으므로 기존 번호는 국가 번호 1로 해석함
+const LEGACY_COUNTRY_CODE: &str = "1";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OwnerPhones::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OwnerPhones::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OwnerPhones::OwnerId).integer().not_null())
+                    .col(ColumnDef::new(OwnerPhones::Kind).string_len(20).not_null())
+                    .col(
+                        ColumnDef::new(OwnerPhones::Number)
+                            .string_len(20)
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(OwnerPhones::Table, OwnerPhones::OwnerId)
+                            .to(Owners::Table, Owners::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_owner_phones_owner_id")
+                    .table(OwnerPhones::Table)
+                    .col(OwnerPhones::OwnerId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 기존 telephone을 E.164로 바꿔 옮긴 뒤 column을 지움.
+        // 해석할 수 없는 번호도 잃지 않도록 입력된 그대로 옮기고, owner 수정 form에서 고치게 함
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        let rows = conn
+            .query_all(
+                backend.build(
+                    Query::select()
+                        .columns([Owners::Id, Owners::Telephone])
+                        .from(Owners::Table)
+                        .order_by(Owners::Id, Order::Asc),
+                ),
+            )
+            .await?;
+
+        let mut insert = Query::insert()
+            .into_table(OwnerPhones::Table)
+            .columns([OwnerPhones::OwnerId, OwnerPhones::Kind, OwnerPhones::Number])
+            .to_owned();
+        let mut has_phones = false;
+        for row in rows {
+            let owner_id: i32 = row.try_get("", "id")?;
+            let telephone: Option<String> = row.try_get("", "telephone")?;
+
+            let Some(telephone) = telephone
+                .as_deref()
+                .map(str::trim)
+                .filter(|telephone| !telephone.is_empty())
+            else {
+                continue;
+            };
+
+            let number = normalize_legacy_telephone(telephone).unwrap_or_else(|| {
+                tracing::warn!(
+                    owner_id,
+                    telephone,
+                    "telephone is not a valid number; copied without normalizing"
+                );
+                telephone.to_string()
+            });
+            insert.values_panic([owner_id.into(), "home".into(), number.into()]);
+            has_phones = true;
+        }
+        if has_phones {
+            conn.execute(backend.build(&insert)).await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Owners::Table)
+                    .drop_column(Owners::Telephone)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Owners::Table)
+                    .add_column(ColumnDef::new(Owners::Telephone).string_len(20))
+                    .to_owned(),
+            )
+            .await?;
+
+        // owner마다 처음 입력한 번호를 telephone으로 되돌림
+        let conn = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        let rows = conn
+            .query_all(
+                backend.build(
+                    Query::select()
+                        .columns([OwnerPhones::OwnerId, OwnerPhones::Number])
+                        .from(OwnerPhones::Table)
+                        .order_by(OwnerPhones::Id, Order::Desc),
+                ),
+            )
+            .await?;
+
+        let mut first_phones = HashMap::new();
+        for row in rows {
+            let owner_id: i32 = row.try_get("", "owner_id")?;
+            let number: String = row.try_get("", "number")?;
+            first_phones.insert(owner_id, number);
+        }
+        for (owner_id, number) in first_phones {
+            conn.execute(
+                backend.build(
+                    Query::update()
+                        .table(Owners::Table)
+                        .value(Owners::Telephone, number)
+                        .and_where(Expr::col(Owners::Id).eq(owner_id)),
+                ),
+            )
+            .await?;
+        }
+
+        manager
+            .drop_table(Table::drop().table(OwnerPhones::Table).to_owned())
+            .await
+    }
+}
+
+/// 이 migration을 작성할 때의 규칙을 고정한 사본. 앱의 전화번호 처리가 바뀌어도
+/// 새 database에서 이 migration이 하는 일은 바뀌지 않아야 하므로 `model::phone`을 쓰지 않음.
+/// 국가 번호 1이 이미 붙어 있으면 다시 붙이지 않고, 북미 번호 자릿수가 아니면 None임
+fn normalize_legacy_telephone(telephone: &str) -> Option<String> {
+    let compact: String = telephone
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    let digits = compact.strip_prefix('+').unwrap_or(&compact);
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let national = match digits.len() {
+        10 => digits,
+        11 => digits.strip_prefix(LEGACY_COUNTRY_CODE)?,
+        _ => return None,
+    };
+    // 북미 번호의 지역 번호와 국번은 0이나 1로 시작하지 않음
+    let is_valid = [0, 3]
+        .iter()
+        .all(|&i| !matches!(national.as_bytes()[i], b'0' | b'1'));
+
+    is_valid.then(|| format!("+{LEGACY_COUNTRY_CODE}{national}"))
+}
+
+#[derive(DeriveIden)]
+pub enum OwnerPhones {
+    Table,
+    Id,
+    OwnerId,
+    Kind,
+    Number,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_legacy_telephone() {
+        for (telephone, expected) in [
+            ("6085551023", Some("+16085551023")),
+            ("608-555-1023", Some("+16085551023")),
+            ("1-608-555-1023", Some("+16085551023")),
+            ("+16085551023", Some("+16085551023")),
+            ("020 7946 0958", None),
+            ("+442079460958", None),
+            ("555-1023", None),
+            ("608-555-CALL", None),
+        ] {
+            assert_eq!(
+                normalize_legacy_telephone(telephone).as_deref(),
+                expected,
+                "{telephone:?}"
+            );
+        }
+    }
+}
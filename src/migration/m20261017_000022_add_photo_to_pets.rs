@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000006_create_pets_table::Pets;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite는 ALTER TABLE 한 번에 column 하나만 추가할 수 있어 나눠서 실행함
+        for column in [Photo::PhotoKey, Photo::ThumbnailKey] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Pets::Table)
+                        .add_column(ColumnDef::new(column).string_len(120))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Photo::PhotoKey, Photo::ThumbnailKey] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Pets::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Photo {
+    PhotoKey,
+    ThumbnailKey,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20261017_000007_create_visits_table::Visits;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VisitAttachments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(VisitAttachments::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::VisitId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::FileName)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::ContentType)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::SizeBytes)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::StorageKey)
+                            .string_len(120)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(VisitAttachments::UploadedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VisitAttachments::Table, VisitAttachments::VisitId)
+                            .to(Visits::Table, Visits::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_visit_attachments_visit_id")
+                    .table(VisitAttachments::Table)
+                    .col(VisitAttachments::VisitId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VisitAttachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum VisitAttachments {
+    Table,
+    Id,
+    VisitId,
+    FileName,
+    ContentType,
+    SizeBytes,
+    StorageKey,
+    UploadedAt,
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+mod m20261017_000001_create_vets_table;
+mod m20261017_000002_create_specialties_table;
+mod m20261017_000003_create_vet_specialties_table;
+mod m20261017_000004_create_types_table;
+mod m20261017_000005_create_owners_table;
+mod m20261017_000006_create_pets_table;
+mod m20261017_000007_create_visits_table;
+mod m20261017_000008_add_status_to_visits;
+mod m20261017_000009_add_vet_to_visits;
+mod m20261017_000010_add_appointment_to_visits;
+mod m20261017_000011_add_active_to_vets;
+mod m20261017_000012_make_specialty_name_unique;
+mod m20261017_000013_make_type_name_unique;
+mod m20261017_000014_create_pet_transfers_table;
+mod m20261017_000015_create_vaccinations_table;
+mod m20261017_000016_create_invoices_table;
+mod m20261017_000017_create_billable_items_table;
+mod m20261017_000018_create_payments_table;
+mod m20261017_000019_create_prescriptions_table;
+mod m20261017_000020_add_contact_details_to_owners;
+mod m20261017_000021_create_owner_phones_table;
+mod m20261017_000022_add_photo_to_pets;
+mod m20261017_000023_create_visit_attachments_table;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261017_000001_create_vets_table::Migration),
+            Box::new(m20261017_000002_create_specialties_table::Migration),
+            Box::new(m20261017_000003_create_vet_specialties_table::Migration),
+            Box::new(m20261017_000004_create_types_table::Migration),
+            Box::new(m20261017_000005_create_owners_table::Migration),
+            Box::new(m20261017_000006_create_pets_table::Migration),
+            Box::new(m20261017_000007_create_visits_table::Migration),
+            Box::new(m20261017_000008_add_status_to_visits::Migration),
+            Box::new(m20261017_000009_add_vet_to_visits::Migration),
+            Box::new(m20261017_000010_add_appointment_to_visits::Migration),
+            Box::new(m20261017_000011_add_active_to_vets::Migration),
+            Box::new(m20261017_000012_make_specialty_name_unique::Migration),
+            Box::new(m20261017_000013_make_type_name_unique::Migration),
+            Box::new(m20261017_000014_create_pet_transfers_table::Migration),
+            Box::new(m20261017_000015_create_vaccinations_table::Migration),
+            Box::new(m20261017_000016_create_invoices_table::Migration),
+            Box::new(m20261017_000017_create_billable_items_table::Migration),
+            Box::new(m20261017_000018_create_payments_table::Migration),
+            Box::new(m20261017_000019_create_prescriptions_table::Migration),
+            Box::new(m20261017_000020_add_contact_details_to_owners::Migration),
+            Box::new(m20261017_000021_create_owner_phones_table::Migration),
+            Box::new(m20261017_000022_add_photo_to_pets::Migration),
+            Box::new(m20261017_000023_create_visit_attachments_table::Migration),
+        ]
+    }
+}
@@ -1,4 +1,5 @@
 use actix_web::{HttpResponse, ResponseError};
+use chrono::NaiveDateTime;
 use thiserror::Error;
 
 use super::error_response::ErrorResponse;
@@ -6,17 +7,47 @@ use super::error_response::ErrorResponse;
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Resource not found: {resource} with id: {id}")]
-    ResourceNotFound { resource: String, id: u32 },
+    ResourceNotFound { resource: String, id: i32 },
 
     #[error(
         "Resource ID mismatch: {resource} - Path ID {path_id} does not match Body ID {body_id}"
     )]
     ResourceIdMismatch {
         resource: String,
-        path_id: u32,
-        body_id: u32,
+        path_id: i32,
+        body_id: i32,
     },
 
+    #[error("Resource in use: {resource} with id: {id} still has {dependents}")]
+    ResourceInUse {
+        resource: String,
+        id: i32,
+        dependents: String,
+    },
+
+    #[error("Duplicate resource: {resource} with {field} '{value}' already exists")]
+    DuplicateResource {
+        resource: String,
+        field: String,
+        value: String,
+    },
+
+    #[error("Invalid state: {resource} with id: {id} is {state}")]
+    InvalidResourceState {
+        resource: String,
+        id: i32,
+        state: String,
+    },
+
+    #[error("Appointment conflict: {starts_at} overlaps an existing booking")]
+    AppointmentConflict {
+        starts_at: NaiveDateTime,
+        next_free_slots: Vec<NaiveDateTime>,
+    },
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(#[from] validator::ValidationErrors),
+
     #[error("Database error: {0}")]
     DbError(#[from] sea_orm::DbErr),
 
@@ -25,6 +56,12 @@ pub enum AppError {
 
     #[error("XML serialize error: {0}")]
     SerializeError(#[from] quick_xml::SeError),
+
+    #[error("Storage error: {0}")]
+    StorageError(#[from] std::io::Error),
+
+    #[error("Blocking task error: {0}")]
+    BlockingError(#[from] actix_web::error::BlockingError),
 }
 
 impl ResponseError for AppError {
@@ -32,9 +69,16 @@ impl ResponseError for AppError {
         let mut res_builder = match self {
             AppError::ResourceNotFound { .. } => HttpResponse::NotFound(),
             AppError::ResourceIdMismatch { .. } => HttpResponse::BadRequest(),
+            AppError::ResourceInUse { .. } => HttpResponse::Conflict(),
+            AppError::DuplicateResource { .. } => HttpResponse::Conflict(),
+            AppError::InvalidResourceState { .. } => HttpResponse::Conflict(),
+            AppError::AppointmentConflict { .. } => HttpResponse::Conflict(),
+            AppError::InvalidRequest(_) => HttpResponse::BadRequest(),
             AppError::DbError(_) => HttpResponse::InternalServerError(),
             AppError::TemplateError(_) => HttpResponse::InternalServerError(),
             AppError::SerializeError(_) => HttpResponse::InternalServerError(),
+            AppError::StorageError(_) => HttpResponse::InternalServerError(),
+            AppError::BlockingError(_) => HttpResponse::InternalServerError(),
         };
 
         let err_body = ErrorResponse::new(self.to_string());
@@ -0,0 +1,104 @@
+/// signature로 알아낸 형식 중 visit에 첨부할 수 있는 형식.
+/// 업로드 요청의 content type은 믿지 않고 파일 내용으로 판단함
+const ATTACHMENT_CONTENT_TYPES: [&str; 8] = [
+    "application/pdf",
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/tiff",
+    "application/dicom",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+];
+
+const MAX_FILE_NAME_CHARS: usize = 255;
+
+/// 파일 앞부분의 signature로 형식을 알아내고, 받을 수 없는 형식이면 None을 반환함.
+/// signature가 없는 CSV 같은 검사 결과 파일은 control 문자가 없는 UTF-8 text인 경우에만 받음
+pub fn sniff_content_type(content: &[u8], file_name: &str) -> Option<&'static str> {
+    if let Some(kind) = infer::get(content) {
+        return ATTACHMENT_CONTENT_TYPES
+            .into_iter()
+            .find(|content_type| *content_type == kind.mime_type());
+    }
+
+    let is_text = std::str::from_utf8(content).is_ok_and(|text| {
+        !text.is_empty()
+            && text
+                .chars()
+                .all(|c| !c.is_control() || matches!(c, '\t' | '\r' | '\n'))
+    });
+    if !is_text {
+        return None;
+    }
+
+    if file_name.to_ascii_lowercase().ends_with(".csv") {
+        Some("text/csv")
+    } else {
+        Some("text/plain")
+    }
+}
+
+/// browser가 보낸 파일 이름에서 경로와 control 문자를 빼고 길이를 제한함.
+/// 남는 글자가 없으면 "attachment"를 씀
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let base_name = file_name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let sanitized: String = base_name
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    let sanitized = sanitized.trim();
+
+    if sanitized.is_empty() || sanitized.chars().all(|c| c == '.') {
+        "attachment".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_content_type() {
+        let cases: [(&[u8], &str, Option<&str>); 6] = [
+            (b"%PDF-1.7\n", "report.pdf", Some("application/pdf")),
+            (
+                b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR",
+                "x-ray.png",
+                Some("image/png"),
+            ),
+            // 이름과 관계없이 내용으로 판단함
+            (b"%PDF-1.7\n", "x-ray.png", Some("application/pdf")),
+            (
+                b"date,glucose\n2026-10-01,5.4\n",
+                "lab.CSV",
+                Some("text/csv"),
+            ),
+            (b"MZ\x90\0\x03\0\0\0", "consent.pdf", None),
+            (b"hello\0world", "notes.txt", None),
+        ];
+
+        for (content, file_name, expected) in cases {
+            assert_eq!(
+                sniff_content_type(content, file_name),
+                expected,
+                "{file_name}"
+            );
+        }
+        assert_eq!(sniff_content_type(b"", "empty.txt"), None);
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("lab results.pdf"), "lab results.pdf");
+        assert_eq!(sanitize_file_name("C:\\scans\\x-ray.png"), "x-ray.png");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("con\"sent\r\n.pdf"), "consent.pdf");
+        assert_eq!(sanitize_file_name(".."), "attachment");
+        assert_eq!(sanitize_file_name(""), "attachment");
+        assert_eq!(sanitize_file_name(&"a".repeat(300)).len(), 255);
+    }
+}
@@ -1,10 +1,14 @@
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 
+use crate::config::request_id;
+
 #[derive(Serialize, Deserialize)]
 pub struct ErrorResponse {
     message: String,
     timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 impl ErrorResponse {
@@ -12,6 +16,7 @@ impl ErrorResponse {
         ErrorResponse {
             message,
             timestamp: Local::now().to_rfc3339(),
+            request_id: request_id::current(),
         }
     }
 }
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Serialize)]
+pub struct ComponentHealth {
+    status: HealthStatus,
+    latency_ms: f64,
+    detail: String,
+}
+
+impl ComponentHealth {
+    pub fn new(result: Result<String, String>, latency_ms: f64) -> Self {
+        let (status, detail) = match result {
+            Ok(detail) => (HealthStatus::Up, detail),
+            Err(detail) => (HealthStatus::Down, detail),
+        };
+
+        ComponentHealth {
+            status,
+            latency_ms,
+            detail,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct HealthResponse {
+    status: HealthStatus,
+    version: &'static str,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    components: BTreeMap<&'static str, ComponentHealth>,
+}
+
+impl HealthResponse {
+    pub fn new(components: BTreeMap<&'static str, ComponentHealth>) -> Self {
+        let status = if components
+            .values()
+            .all(|component| component.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+
+        HealthResponse {
+            status,
+            version: env!("CARGO_PKG_VERSION"),
+            components,
+        }
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+}
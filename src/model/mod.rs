@@ -1,3 +1,8 @@
 pub mod app_error;
+pub mod attachment;
 pub mod error_response;
+pub mod health_response;
+pub mod money;
 pub mod page;
+pub mod phone;
+pub mod photo;
@@ -0,0 +1,121 @@
+use std::{
+    fmt::{self, Display},
+    iter::Sum,
+    ops::{Add, Sub},
+};
+
+use serde::{Serialize, Serializer};
+
+// 입력받을 수 있는 가장 큰 금액 (cent). 합계를 계산할 때 overflow가 나지 않도록 제한함
+const MAX_CENTS: i64 = 10_000_000_000;
+
+/// 금액을 최소 화폐 단위(cent)의 정수로 다룸. template에는 "12.34" 형식의 문자열로 serialize 함
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// "12", "12.3", "12.34" 형식만 허용하며 음수와 소수점 아래 세 자리 이상은 None임
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (units, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if units.is_empty() || !is_digits(units) || fraction.len() > 2 || !is_digits(fraction) {
+            return None;
+        }
+
+        let units: i64 = units.parse().ok()?;
+        let fraction: i64 = format!("{fraction:0<2}").parse().ok()?;
+        let cents = units.checked_mul(100)?.checked_add(fraction)?;
+
+        (cents <= MAX_CENTS).then_some(Money(cents))
+    }
+
+    pub fn times(self, quantity: i32) -> Self {
+        Money(self.0 * i64::from(quantity))
+    }
+
+    /// basis point(0.01%) 단위 세율의 세금. cent 아래는 반올림함
+    pub fn tax(self, rate_basis_points: u32) -> Self {
+        Money((self.0 * i64::from(rate_basis_points) + 5_000).div_euclid(10_000))
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, rhs: Money) -> Money {
+        Money(self.0 - rhs.0)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let cents = self.0.unsigned_abs();
+
+        write!(f, "{sign}{}.{:02}", cents / 100, cents % 100)
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Money::parse("12"), Some(Money(1200)));
+        assert_eq!(Money::parse("12.3"), Some(Money(1230)));
+        assert_eq!(Money::parse(" 12.34 "), Some(Money(1234)));
+        assert_eq!(Money::parse("0.05"), Some(Money(5)));
+
+        for invalid in ["", ".5", "12.345", "-1", "1,000", "abc"] {
+            assert_eq!(Money::parse(invalid), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_tax_rounds_to_nearest_cent() {
+        assert_eq!(Money(1000).tax(1000), Money(100));
+        assert_eq!(Money(1005).tax(1000), Money(101));
+        assert_eq!(Money(1004).tax(1000), Money(100));
+        assert_eq!(Money(1234).tax(0), Money::ZERO);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Money(123456).to_string(), "1234.56");
+        assert_eq!(Money(5).to_string(), "0.05");
+        assert_eq!(Money(-250).to_string(), "-2.50");
+    }
+}
@@ -6,8 +6,8 @@ pub struct Page {
 }
 
 impl Page {
-    pub fn new(page: u64, total_count: u64) -> Self {
-        let total_pages = total_count.div_ceil(5);
+    pub fn new(page: u64, size: u64, total_count: u64) -> Self {
+        let total_pages = total_count.div_ceil(size.max(1));
         let start_page = page / 5 * 5 + 1;
         let end_page = (start_page + 4).min(total_pages);
 
@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+use phonenumber::{country, metadata::DATABASE, Mode, PhoneNumber};
+
+/// 입력한 전화번호를 E.164 형식("+16085551023")으로 바꿈. 공백, '-', '.', 괄호는 무시함.
+/// '+'나 '00'으로 시작하면 국제 번호로, 아니면 `default_country_code` 나라의 국내 번호로 보고
+/// 그 나라의 번호 규칙(trunk prefix, 국가 번호를 포함한 국내 표기, 자릿수)으로 해석함.
+/// 해석할 수 없거나 그 나라에 없는 번호면 None임
+pub fn normalize_phone(input: &str, default_country_code: &str) -> Option<String> {
+    let compact: String = input
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+
+    let (country, digits) = if let Some(international) = compact.strip_prefix('+') {
+        (None, international)
+    } else if let Some(international) = compact.strip_prefix("00") {
+        (None, international)
+    } else {
+        (
+            Some(default_country(default_country_code)?),
+            compact.as_str(),
+        )
+    };
+    // 글자를 숫자로 바꾸는 vanity 번호나 내선 번호는 받지 않음
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let number = match country {
+        Some(country) => phonenumber::parse(Some(country), digits),
+        None => phonenumber::parse(None, format!("+{digits}")),
+    }
+    .ok()?;
+
+    is_valid(&number).then(|| number.format().mode(Mode::E164).to_string())
+}
+
+/// 국가 번호를 쓰는 나라가 하나 이상 있는지 확인함
+pub fn is_known_country_code(country_code: &str) -> bool {
+    default_country(country_code).is_some()
+}
+
+/// 국가 번호를 같이 쓰는 나라가 여럿이면("1"은 미국, 캐나다 등) 대표 나라의 규칙을 씀
+fn default_country(country_code: &str) -> Option<country::Id> {
+    if country_code.starts_with('0') {
+        return None;
+    }
+    let code: u16 = country_code.parse().ok()?;
+    let region = DATABASE.region(&code)?.into_iter().next()?;
+
+    country::Id::from_str(region).ok()
+}
+
+fn is_valid(number: &PhoneNumber) -> bool {
+    phonenumber::is_valid(number) && number.extension().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(
+            normalize_phone("6085551023", "1").as_deref(),
+            Some("+16085551023")
+        );
+        assert_eq!(
+            normalize_phone("(608) 555-1023", "1").as_deref(),
+            Some("+16085551023")
+        );
+        assert_eq!(
+            normalize_phone("+44 20 7946 0958", "1").as_deref(),
+            Some("+442079460958")
+        );
+        assert_eq!(
+            normalize_phone("0044 20 7946 0958", "1").as_deref(),
+            Some("+442079460958")
+        );
+        assert_eq!(
+            normalize_phone("020 7946 0958", "44").as_deref(),
+            Some("+442079460958")
+        );
+        assert_eq!(
+            normalize_phone("010-1234-5678", "82").as_deref(),
+            Some("+821012345678")
+        );
+
+        for invalid in [
+            "",
+            "555-10",
+            "+0 123 456 789",
+            "+1 608 555 1023 45678",
+            "608-555-CALL",
+        ] {
+            assert_eq!(normalize_phone(invalid, "1"), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_phone_with_country_code_in_national_format() {
+        // 국내 표기에 이미 국가 번호가 들어 있으면 다시 붙이지 않음
+        assert_eq!(
+            normalize_phone("1-608-555-1023", "1").as_deref(),
+            Some("+16085551023")
+        );
+        assert_eq!(
+            normalize_phone("1 (608) 555-1023", "1").as_deref(),
+            Some("+16085551023")
+        );
+    }
+
+    #[test]
+    fn test_normalize_phone_rejects_numbers_of_other_countries() {
+        // 영국 국내 표기는 미국 번호로 해석할 수 없음
+        assert_eq!(normalize_phone("020 7946 0958", "1"), None);
+        assert_eq!(normalize_phone("1 608 555 1023", "44"), None);
+        assert_eq!(normalize_phone("608 555 102", "1"), None);
+    }
+
+    #[test]
+    fn test_is_known_country_code() {
+        assert!(is_known_country_code("1"));
+        assert!(is_known_country_code("44"));
+        assert!(!is_known_country_code("999"));
+        assert!(!is_known_country_code("01"));
+    }
+}
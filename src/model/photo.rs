@@ -0,0 +1,149 @@
+use std::io::Cursor;
+
+use image::{
+    imageops::FilterType, DynamicImage, ImageDecoder, ImageError, ImageFormat, ImageReader, Limits,
+};
+use thiserror::Error;
+
+/// pet 사진으로 받는 형식. 업로드 요청의 content type과 파일 내용이 모두 이 중 하나여야 함
+pub const PHOTO_CONTENT_TYPES: [&str; 4] = ["image/jpeg", "image/png", "image/gif", "image/webp"];
+
+const PHOTO_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Jpeg,
+    ImageFormat::Png,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+
+// 파일 크기가 작아도 해상도가 매우 크면 decode할 때 메모리를 많이 쓰므로 제한함
+const MAX_DIMENSION: u32 = 10_000;
+
+#[derive(Debug, Error)]
+pub enum PhotoError {
+    #[error("unsupported image format")]
+    UnsupportedFormat,
+    #[error("invalid image: {0}")]
+    Invalid(#[from] ImageError),
+}
+
+/// 저장할 크기로 줄이고 다시 encode한 사진과 thumbnail.
+/// 투명한 부분이 있으면 PNG, 아니면 JPEG로 저장하며 EXIF 등 metadata는 남기지 않음
+pub struct ProcessedPhoto {
+    pub image: Vec<u8>,
+    pub thumbnail: Vec<u8>,
+    pub extension: &'static str,
+}
+
+pub fn process_photo(
+    content: &[u8],
+    display_size: u32,
+    thumbnail_size: u32,
+) -> Result<ProcessedPhoto, PhotoError> {
+    let format = image::guess_format(content).map_err(|_| PhotoError::UnsupportedFormat)?;
+    if !PHOTO_FORMATS.contains(&format) {
+        return Err(PhotoError::UnsupportedFormat);
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    let mut reader = ImageReader::with_format(Cursor::new(content), format);
+    reader.limits(limits);
+
+    // 휴대폰 사진은 회전 정보를 EXIF에만 담으므로 metadata를 버리기 전에 적용함
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut photo = DynamicImage::from_decoder(decoder)?;
+    photo.apply_orientation(orientation);
+
+    if photo.width() > display_size || photo.height() > display_size {
+        photo = photo.resize(display_size, display_size, FilterType::Lanczos3);
+    }
+    let thumbnail = photo.thumbnail(thumbnail_size, thumbnail_size);
+
+    let (format, extension) = if photo.color().has_alpha() {
+        (ImageFormat::Png, "png")
+    } else {
+        (ImageFormat::Jpeg, "jpg")
+    };
+
+    Ok(ProcessedPhoto {
+        image: encode(photo, format)?,
+        thumbnail: encode(thumbnail, format)?,
+        extension,
+    })
+}
+
+fn encode(photo: DynamicImage, format: ImageFormat) -> Result<Vec<u8>, ImageError> {
+    // JPEG은 8bit RGB만, PNG는 여기서 8bit RGBA로 맞춰 저장함
+    let photo = match format {
+        ImageFormat::Jpeg => DynamicImage::ImageRgb8(photo.to_rgb8()),
+        _ => DynamicImage::ImageRgba8(photo.to_rgba8()),
+    };
+
+    let mut content = Cursor::new(Vec::new());
+    photo.write_to(&mut content, format)?;
+
+    Ok(content.into_inner())
+}
+
+/// 저장한 사진 key의 확장자로 응답의 content type을 정함
+pub fn photo_content_type(key: &str) -> &'static str {
+    if key.ends_with(".png") {
+        "image/png"
+    } else {
+        "image/jpeg"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GenericImageView, Rgb, RgbImage, Rgba, RgbaImage};
+
+    use super::*;
+
+    fn encoded(photo: DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut content = Cursor::new(Vec::new());
+        photo.write_to(&mut content, format).unwrap();
+
+        content.into_inner()
+    }
+
+    #[test]
+    fn test_process_photo() {
+        let photo = DynamicImage::ImageRgb8(RgbImage::from_pixel(1200, 600, Rgb([200, 80, 40])));
+        let processed = process_photo(&encoded(photo, ImageFormat::Png), 800, 160).unwrap();
+
+        assert_eq!(processed.extension, "jpg");
+        let image = image::load_from_memory(&processed.image).unwrap();
+        assert_eq!(image.dimensions(), (800, 400));
+        let thumbnail = image::load_from_memory(&processed.thumbnail).unwrap();
+        assert_eq!(thumbnail.dimensions(), (160, 80));
+
+        // 이미 작은 사진은 키우지 않고, 투명한 사진은 PNG로 저장함
+        let photo = DynamicImage::ImageRgba8(RgbaImage::from_pixel(100, 50, Rgba([0, 0, 0, 0])));
+        let processed = process_photo(&encoded(photo, ImageFormat::Png), 800, 160).unwrap();
+
+        assert_eq!(processed.extension, "png");
+        let image = image::load_from_memory(&processed.image).unwrap();
+        assert_eq!(image.dimensions(), (100, 50));
+    }
+
+    #[test]
+    fn test_process_photo_rejects_other_content() {
+        assert!(matches!(
+            process_photo(b"not an image", 800, 160),
+            Err(PhotoError::UnsupportedFormat)
+        ));
+        // BMP는 형식은 알아보지만 받지 않음
+        assert!(matches!(
+            process_photo(b"BM\0\0\0\0\0\0\0\0", 800, 160),
+            Err(PhotoError::UnsupportedFormat)
+        ));
+        // PNG signature 뒤가 깨진 파일
+        assert!(matches!(
+            process_photo(b"\x89PNG\r\n\x1a\n broken", 800, 160),
+            Err(PhotoError::Invalid(_))
+        ));
+    }
+}
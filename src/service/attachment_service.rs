@@ -0,0 +1,178 @@
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, JoinType,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+use tracing::{field, instrument, warn, Span};
+use uuid::Uuid;
+
+use crate::{
+    domain::owner::{pet, visit, visit_attachment},
+    model::app_error::AppError,
+    service::visit_service::VisitService,
+    storage::Storage,
+};
+
+pub struct AttachmentService;
+
+/// 업로드한 파일. content type은 파일 내용으로 알아낸 값임
+pub struct NewAttachment {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub content: Vec<u8>,
+}
+
+impl AttachmentService {
+    /// 파일을 저장소에 먼저 저장한 뒤 기록을 남김. 기록에 실패하면 저장한 파일을 지움
+    #[instrument(skip(conn, storage, attachment), fields(attachment_id = field::Empty))]
+    pub async fn save_attachment(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        attachment: NewAttachment,
+    ) -> Result<visit_attachment::Model, AppError> {
+        VisitService::fetch_visit_of_pet(conn, owner_id, pet_id, visit_id).await?;
+
+        let storage_key = format!("visits/{visit_id}/{}", Uuid::new_v4());
+        storage.put(&storage_key, &attachment.content).await?;
+
+        let attachment_active_model = visit_attachment::ActiveModel {
+            visit_id: ActiveValue::Set(visit_id),
+            file_name: ActiveValue::Set(attachment.file_name),
+            content_type: ActiveValue::Set(attachment.content_type.to_string()),
+            size_bytes: ActiveValue::Set(attachment.content.len() as i64),
+            storage_key: ActiveValue::Set(storage_key.clone()),
+            uploaded_at: ActiveValue::Set(Local::now().naive_local()),
+            ..Default::default()
+        };
+
+        match attachment_active_model.insert(conn).await {
+            Ok(new_attachment) => {
+                Span::current().record("attachment_id", new_attachment.id);
+                Ok(new_attachment)
+            }
+            Err(e) => {
+                Self::delete_files(storage, &[storage_key]).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// owner의 모든 visit의 첨부 파일을 올린 순서대로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_attachments_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<Vec<visit_attachment::Model>, AppError> {
+        let attachments = visit_attachment::Entity::find()
+            .join(
+                JoinType::InnerJoin,
+                visit_attachment::Relation::Visits.def(),
+            )
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .order_by_asc(visit_attachment::Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(attachments)
+    }
+
+    /// 첨부 파일이 path의 visit에, visit이 pet에, pet이 owner에 속할 때만 반환하고 아니면 not found로 처리함
+    #[instrument(skip(conn))]
+    pub async fn fetch_attachment_of_visit(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        attachment_id: i32,
+    ) -> Result<visit_attachment::Model, AppError> {
+        visit_attachment::Entity::find_by_id(attachment_id)
+            .join(
+                JoinType::InnerJoin,
+                visit_attachment::Relation::Visits.def(),
+            )
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .filter(visit_attachment::Column::VisitId.eq(visit_id))
+            .filter(visit::Column::PetId.eq(pet_id))
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "attachment".to_string(),
+                id: attachment_id,
+            })
+    }
+
+    #[instrument(skip(storage, attachment), fields(attachment_id = attachment.id))]
+    pub async fn read_attachment(
+        storage: &dyn Storage,
+        attachment: &visit_attachment::Model,
+    ) -> Result<Vec<u8>, AppError> {
+        storage
+            .get(&attachment.storage_key)
+            .await
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => AppError::ResourceNotFound {
+                    resource: "attachment".to_string(),
+                    id: attachment.id,
+                },
+                _ => AppError::StorageError(e),
+            })
+    }
+
+    /// 기록을 삭제한 뒤 파일을 지우고, 삭제한 첨부 파일을 반환함
+    #[instrument(skip(conn, storage))]
+    pub async fn delete_attachment(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        attachment_id: i32,
+    ) -> Result<visit_attachment::Model, AppError> {
+        let attachment =
+            Self::fetch_attachment_of_visit(conn, owner_id, pet_id, visit_id, attachment_id)
+                .await?;
+
+        visit_attachment::Entity::delete_by_id(attachment_id)
+            .exec(conn)
+            .await?;
+        Self::delete_files(storage, std::slice::from_ref(&attachment.storage_key)).await;
+
+        Ok(attachment)
+    }
+
+    /// visit을 삭제할 때 같은 transaction에서 첨부 파일 기록을 삭제하고 파일 key를 반환함.
+    /// 파일은 transaction이 끝난 뒤 delete_files로 지움
+    pub async fn delete_attachments_of_visits<C: ConnectionTrait>(
+        conn: &C,
+        visit_ids: &[i32],
+    ) -> Result<Vec<String>, AppError> {
+        let storage_keys = visit_attachment::Entity::find()
+            .select_only()
+            .column(visit_attachment::Column::StorageKey)
+            .filter(visit_attachment::Column::VisitId.is_in(visit_ids.iter().copied()))
+            .into_tuple::<String>()
+            .all(conn)
+            .await?;
+
+        visit_attachment::Entity::delete_many()
+            .filter(visit_attachment::Column::VisitId.is_in(visit_ids.iter().copied()))
+            .exec(conn)
+            .await?;
+
+        Ok(storage_keys)
+    }
+
+    /// 남은 파일은 공간만 차지할 뿐 화면에는 보이지 않으므로 삭제에 실패해도 log만 남김
+    pub async fn delete_files(storage: &dyn Storage, storage_keys: &[String]) {
+        for key in storage_keys {
+            if let Err(e) = storage.delete(key).await {
+                warn!(key = key.as_str(), error = %e, "failed to delete attachment file");
+            }
+        }
+    }
+}
@@ -0,0 +1,529 @@
+use chrono::Local;
+use sea_orm::{
+    prelude::{Date, Expr},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbConn, EntityTrait,
+    FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Select,
+    TransactionTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, Span};
+
+use crate::{
+    domain::owner::{
+        billable_item::{self, BillableItemKind},
+        invoice, owners,
+        payment::{self, PaymentMethod},
+        pet, visit,
+    },
+    model::{app_error::AppError, money::Money},
+};
+
+pub struct BillingService;
+
+/// visit에 추가할 청구 항목
+#[derive(Clone, Debug)]
+pub struct NewBillableItem {
+    pub kind: BillableItemKind,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: Money,
+}
+
+/// 직원이 직접 입력하는 결제
+#[derive(Clone, Debug)]
+pub struct NewPayment {
+    pub paid_on: Date,
+    pub amount: Money,
+    pub method: PaymentMethod,
+    pub reference: Option<String>,
+}
+
+#[derive(FromQueryResult)]
+struct BillableItemRow {
+    id: i32,
+    visit_id: i32,
+    visit_date: Option<Date>,
+    pet_name: Option<String>,
+    kind: BillableItemKind,
+    description: String,
+    quantity: i32,
+    unit_price_cents: i64,
+    invoice_id: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct BillableItem {
+    id: i32,
+    visit_id: i32,
+    visit_date: Option<Date>,
+    pet_name: Option<String>,
+    kind: BillableItemKind,
+    description: String,
+    quantity: i32,
+    unit_price: Money,
+    pub amount: Money,
+    invoice_id: Option<i32>,
+}
+
+impl From<BillableItemRow> for BillableItem {
+    fn from(row: BillableItemRow) -> Self {
+        let unit_price = Money::from_cents(row.unit_price_cents);
+
+        BillableItem {
+            id: row.id,
+            visit_id: row.visit_id,
+            visit_date: row.visit_date,
+            pet_name: row.pet_name,
+            kind: row.kind,
+            description: row.description,
+            quantity: row.quantity,
+            unit_price,
+            amount: unit_price.times(row.quantity),
+            invoice_id: row.invoice_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Payment {
+    id: i32,
+    paid_on: Date,
+    amount: Money,
+    method: PaymentMethod,
+    reference: Option<String>,
+}
+
+impl From<payment::Model> for Payment {
+    fn from(model: payment::Model) -> Self {
+        Payment {
+            id: model.id,
+            paid_on: model.paid_on,
+            amount: Money::from_cents(model.amount_cents),
+            method: model.method,
+            reference: model.reference,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct InvoiceSummary {
+    id: i32,
+    issued_on: Date,
+    total: Money,
+    paid: Money,
+    outstanding: Money,
+}
+
+#[derive(Serialize)]
+pub struct InvoiceDetails {
+    id: i32,
+    owner_id: i32,
+    issued_on: Date,
+    subtotal: Money,
+    /// "10.00" 형식의 백분율
+    tax_rate_percent: String,
+    tax: Money,
+    total: Money,
+    paid: Money,
+    outstanding: Money,
+    items: Vec<BillableItem>,
+    payments: Vec<Payment>,
+}
+
+/// owner에게 발행한 invoice 합계와 결제 합계, 아직 청구하지 않은 금액
+#[derive(Serialize)]
+pub struct OwnerBalance {
+    invoiced: Money,
+    paid: Money,
+    outstanding: Money,
+    unbilled: Money,
+}
+
+impl BillingService {
+    /// path의 owner와 pet에 속한 visit에만 항목을 추가함
+    #[instrument(skip_all, fields(visit_id = visit_id, billable_item_id = field::Empty))]
+    pub async fn add_item(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        new_item: NewBillableItem,
+    ) -> Result<billable_item::Model, AppError> {
+        Self::ensure_visit_of_owner(conn, owner_id, pet_id, visit_id).await?;
+
+        let item = billable_item::ActiveModel {
+            visit_id: ActiveValue::Set(visit_id),
+            kind: ActiveValue::Set(new_item.kind),
+            description: ActiveValue::Set(new_item.description),
+            quantity: ActiveValue::Set(new_item.quantity),
+            unit_price_cents: ActiveValue::Set(new_item.unit_price.cents()),
+            invoice_id: ActiveValue::Set(None),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+        Span::current().record("billable_item_id", item.id);
+
+        Ok(item)
+    }
+
+    /// 청구한 항목은 invoice 금액이 바뀌므로 삭제하지 않음
+    #[instrument(skip(conn))]
+    pub async fn delete_item(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        item_id: i32,
+    ) -> Result<(), AppError> {
+        Self::ensure_visit_of_owner(conn, owner_id, pet_id, visit_id).await?;
+
+        let item = billable_item::Entity::find_by_id(item_id)
+            .filter(billable_item::Column::VisitId.eq(visit_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "billable item".to_string(),
+                id: item_id,
+            })?;
+
+        if let Some(invoice_id) = item.invoice_id {
+            return Err(AppError::InvalidResourceState {
+                resource: "billable item".to_string(),
+                id: item_id,
+                state: format!("already billed on invoice {invoice_id}"),
+            });
+        }
+
+        billable_item::Entity::delete_by_id(item_id)
+            .exec(conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn ensure_visit_of_owner(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+    ) -> Result<(), AppError> {
+        visit::Entity::find_by_id(visit_id)
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .filter(visit::Column::PetId.eq(pet_id))
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "visit".to_string(),
+                id: visit_id,
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_items_by_visit(
+        conn: &DbConn,
+        visit_id: i32,
+    ) -> Result<Vec<BillableItem>, AppError> {
+        let items = Self::select_items()
+            .filter(billable_item::Column::VisitId.eq(visit_id))
+            .into_model::<BillableItemRow>()
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(BillableItem::from)
+            .collect();
+
+        Ok(items)
+    }
+
+    /// owner가 지금 가진 pet의 visit 중 아직 청구하지 않은 항목
+    #[instrument(skip(conn))]
+    pub async fn fetch_unbilled_items_by_owner<C: ConnectionTrait>(
+        conn: &C,
+        owner_id: i32,
+    ) -> Result<Vec<BillableItem>, AppError> {
+        let items = Self::select_items()
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .filter(billable_item::Column::InvoiceId.is_null())
+            .into_model::<BillableItemRow>()
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(BillableItem::from)
+            .collect();
+
+        Ok(items)
+    }
+
+    fn select_items() -> Select<billable_item::Entity> {
+        billable_item::Entity::find()
+            .join(JoinType::InnerJoin, billable_item::Relation::Visits.def())
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .select_only()
+            .column(billable_item::Column::Id)
+            .column(billable_item::Column::VisitId)
+            .column(visit::Column::VisitDate)
+            .column_as(pet::Column::Name, "pet_name")
+            .column(billable_item::Column::Kind)
+            .column(billable_item::Column::Description)
+            .column(billable_item::Column::Quantity)
+            .column(billable_item::Column::UnitPriceCents)
+            .column(billable_item::Column::InvoiceId)
+            .order_by_asc(visit::Column::VisitDate)
+            .order_by_asc(billable_item::Column::Id)
+    }
+
+    /// 아직 청구하지 않은 owner의 모든 항목을 하나의 invoice로 묶음
+    #[instrument(skip(conn), fields(invoice_id = field::Empty))]
+    pub async fn create_invoice(
+        conn: &DbConn,
+        owner_id: i32,
+        tax_rate_basis_points: u32,
+    ) -> Result<invoice::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        owners::Entity::find_by_id(owner_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "owner".to_string(),
+                id: owner_id,
+            })?;
+
+        let items = Self::fetch_unbilled_items_by_owner(&txn, owner_id).await?;
+        if items.is_empty() {
+            return Err(AppError::InvalidResourceState {
+                resource: "owner".to_string(),
+                id: owner_id,
+                state: "without unbilled items".to_string(),
+            });
+        }
+
+        let subtotal: Money = items.iter().map(|item| item.amount).sum();
+        let tax = subtotal.tax(tax_rate_basis_points);
+
+        let invoice = invoice::ActiveModel {
+            owner_id: ActiveValue::Set(owner_id),
+            issued_on: ActiveValue::Set(Local::now().date_naive()),
+            subtotal_cents: ActiveValue::Set(subtotal.cents()),
+            // config 검증에서 10000 이하로 제한하므로 i32 범위 안임
+            tax_rate_basis_points: ActiveValue::Set(tax_rate_basis_points as i32),
+            tax_cents: ActiveValue::Set(tax.cents()),
+            total_cents: ActiveValue::Set((subtotal + tax).cents()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        Span::current().record("invoice_id", invoice.id);
+
+        // 동시에 다른 요청이 같은 항목을 청구했다면 rollback함
+        let result = billable_item::Entity::update_many()
+            .col_expr(billable_item::Column::InvoiceId, Expr::value(invoice.id))
+            .filter(billable_item::Column::Id.is_in(items.iter().map(|item| item.id)))
+            .filter(billable_item::Column::InvoiceId.is_null())
+            .exec(&txn)
+            .await?;
+        if result.rows_affected != items.len() as u64 {
+            return Err(AppError::InvalidResourceState {
+                resource: "owner".to_string(),
+                id: owner_id,
+                state: "with items invoiced concurrently".to_string(),
+            });
+        }
+
+        txn.commit().await?;
+
+        Ok(invoice)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_invoices_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<Vec<InvoiceSummary>, AppError> {
+        let invoices = invoice::Entity::find()
+            .filter(invoice::Column::OwnerId.eq(owner_id))
+            .order_by_desc(invoice::Column::IssuedOn)
+            .order_by_desc(invoice::Column::Id)
+            .all(conn)
+            .await?;
+        let payments =
+            Self::fetch_payments_by_invoices(conn, invoices.iter().map(|i| i.id)).await?;
+
+        let summaries = invoices
+            .into_iter()
+            .map(|invoice| {
+                let total = Money::from_cents(invoice.total_cents);
+                let paid = sum_payments(&payments, invoice.id);
+
+                InvoiceSummary {
+                    id: invoice.id,
+                    issued_on: invoice.issued_on,
+                    total,
+                    paid,
+                    outstanding: total - paid,
+                }
+            })
+            .collect();
+
+        Ok(summaries)
+    }
+
+    /// 다른 owner의 invoice id로 조회하면 not found로 처리함
+    #[instrument(skip(conn))]
+    pub async fn fetch_invoice(
+        conn: &DbConn,
+        owner_id: i32,
+        invoice_id: i32,
+    ) -> Result<InvoiceDetails, AppError> {
+        let invoice = Self::fetch_invoice_of_owner(conn, owner_id, invoice_id).await?;
+
+        let items = Self::select_items()
+            .filter(billable_item::Column::InvoiceId.eq(invoice_id))
+            .into_model::<BillableItemRow>()
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(BillableItem::from)
+            .collect();
+        let payments = Self::fetch_payments_by_invoices(conn, [invoice_id]).await?;
+
+        let total = Money::from_cents(invoice.total_cents);
+        let paid = sum_payments(&payments, invoice_id);
+        let rate = invoice.tax_rate_basis_points;
+
+        Ok(InvoiceDetails {
+            id: invoice.id,
+            owner_id: invoice.owner_id,
+            issued_on: invoice.issued_on,
+            subtotal: Money::from_cents(invoice.subtotal_cents),
+            tax_rate_percent: format!("{}.{:02}", rate / 100, rate % 100),
+            tax: Money::from_cents(invoice.tax_cents),
+            total,
+            paid,
+            outstanding: total - paid,
+            items,
+            payments: payments.into_iter().map(Payment::from).collect(),
+        })
+    }
+
+    async fn fetch_invoice_of_owner<C: ConnectionTrait>(
+        conn: &C,
+        owner_id: i32,
+        invoice_id: i32,
+    ) -> Result<invoice::Model, AppError> {
+        Self::select_invoice_of_owner(owner_id, invoice_id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "invoice".to_string(),
+                id: invoice_id,
+            })
+    }
+
+    fn select_invoice_of_owner(owner_id: i32, invoice_id: i32) -> Select<invoice::Entity> {
+        invoice::Entity::find_by_id(invoice_id).filter(invoice::Column::OwnerId.eq(owner_id))
+    }
+
+    async fn fetch_payments_by_invoices<C: ConnectionTrait>(
+        conn: &C,
+        invoice_ids: impl IntoIterator<Item = i32>,
+    ) -> Result<Vec<payment::Model>, AppError> {
+        let payments = payment::Entity::find()
+            .filter(payment::Column::InvoiceId.is_in(invoice_ids))
+            .order_by_asc(payment::Column::PaidOn)
+            .order_by_asc(payment::Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(payments)
+    }
+
+    /// 남은 금액보다 많이 결제할 수 없음
+    #[instrument(skip_all, fields(invoice_id = invoice_id, payment_id = field::Empty))]
+    pub async fn record_payment(
+        conn: &DbConn,
+        owner_id: i32,
+        invoice_id: i32,
+        new_payment: NewPayment,
+    ) -> Result<payment::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        // 동시 결제로 잔액을 넘기지 않도록 invoice row를 잠금
+        let invoice = Self::select_invoice_of_owner(owner_id, invoice_id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "invoice".to_string(),
+                id: invoice_id,
+            })?;
+        let payments = Self::fetch_payments_by_invoices(&txn, [invoice_id]).await?;
+        let outstanding =
+            Money::from_cents(invoice.total_cents) - sum_payments(&payments, invoice_id);
+        if new_payment.amount > outstanding {
+            return Err(AppError::InvalidResourceState {
+                resource: "invoice".to_string(),
+                id: invoice_id,
+                state: format!("outstanding {outstanding}"),
+            });
+        }
+
+        let payment = payment::ActiveModel {
+            invoice_id: ActiveValue::Set(invoice_id),
+            paid_on: ActiveValue::Set(new_payment.paid_on),
+            amount_cents: ActiveValue::Set(new_payment.amount.cents()),
+            method: ActiveValue::Set(new_payment.method),
+            reference: ActiveValue::Set(new_payment.reference),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        Span::current().record("payment_id", payment.id);
+
+        txn.commit().await?;
+
+        Ok(payment)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_owner_balance(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<OwnerBalance, AppError> {
+        let invoices = invoice::Entity::find()
+            .filter(invoice::Column::OwnerId.eq(owner_id))
+            .all(conn)
+            .await?;
+        let payments =
+            Self::fetch_payments_by_invoices(conn, invoices.iter().map(|i| i.id)).await?;
+        let unbilled_items = Self::fetch_unbilled_items_by_owner(conn, owner_id).await?;
+
+        let invoiced: Money = invoices
+            .iter()
+            .map(|invoice| Money::from_cents(invoice.total_cents))
+            .sum();
+        let paid: Money = payments
+            .iter()
+            .map(|payment| Money::from_cents(payment.amount_cents))
+            .sum();
+
+        Ok(OwnerBalance {
+            invoiced,
+            paid,
+            outstanding: invoiced - paid,
+            unbilled: unbilled_items.iter().map(|item| item.amount).sum(),
+        })
+    }
+}
+
+fn sum_payments(payments: &[payment::Model], invoice_id: i32) -> Money {
+    payments
+        .iter()
+        .filter(|payment| payment.invoice_id == invoice_id)
+        .map(|payment| Money::from_cents(payment.amount_cents))
+        .sum()
+}
@@ -1,4 +1,9 @@
+pub mod attachment_service;
+pub mod billing_service;
 pub mod owner_service;
 pub mod pet_service;
+pub mod pet_type_service;
+pub mod specialty_service;
+pub mod vaccination_service;
 pub mod vet_service;
 pub mod visit_service;
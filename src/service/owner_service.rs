@@ -1,85 +1,193 @@
 use std::collections::HashMap;
 
 use sea_orm::{
-    prelude::{Date, Expr},
-    ActiveModelTrait, ActiveValue, ColumnTrait, DbConn, EntityTrait, FromQueryResult, JoinType,
-    PaginatorTrait, QueryFilter, QuerySelect, RelationTrait,
+    prelude::{Date, DateTime, Expr},
+    sea_query::SimpleExpr,
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseTransaction, DbBackend,
+    DbConn, EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, TransactionTrait,
 };
 use serde::Serialize;
+use tracing::{field, instrument, Span};
 
 use crate::{
-    domain::owner::{owners, pet, types, visit},
+    config::metrics::METRICS,
+    domain::{
+        owner::{
+            invoice,
+            owner_phone::{self, PhoneKind},
+            owners::{self, ContactMethod},
+            pet, pet_transfer, types,
+            visit::{self, VisitStatus},
+            visit_attachment,
+        },
+        veterinarian::vet,
+    },
     model::app_error::AppError,
+    service::{billing_service::OwnerBalance, visit_service::Prescription},
 };
 
 pub struct OwnerService;
 
+/// owner form에서 입력한 연락처. 전화번호는 모두 E.164 형식임
+#[derive(Clone, Debug)]
+pub struct ContactDetails {
+    pub email: Option<String>,
+    pub phones: Vec<(PhoneKind, String)>,
+    pub preferred_contact: ContactMethod,
+    pub emergency_contact_name: Option<String>,
+    pub emergency_contact_phone: Option<String>,
+}
+
 #[derive(Serialize, FromQueryResult)]
 pub struct OwnerWithPetsAndTypesAndVisitsQueryResult {
-    pub owner_id: u32,
+    pub owner_id: i32,
     first_name: Option<String>,
     last_name: Option<String>,
     address: Option<String>,
     city: Option<String>,
-    telephone: Option<String>,
-    pet_id: Option<u32>,
+    email: Option<String>,
+    preferred_contact: ContactMethod,
+    emergency_contact_name: Option<String>,
+    emergency_contact_phone: Option<String>,
+    pet_id: Option<i32>,
     pet_name: Option<String>,
     birth_date: Option<Date>,
-    type_id: Option<u32>,
+    thumbnail_key: Option<String>,
+    type_id: Option<i32>,
     type_name: Option<String>,
-    visit_id: Option<u32>,
+    visit_id: Option<i32>,
     visit_date: Option<Date>,
     description: Option<String>,
+    status: Option<VisitStatus>,
+    vet_id: Option<i32>,
+    vet_first_name: Option<String>,
+    vet_last_name: Option<String>,
+    starts_at: Option<DateTime>,
+    duration_minutes: Option<i32>,
+    room: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct OwnerWithPetsAndTypesAndVisits {
-    owner_id: u32,
+    owner_id: i32,
     first_name: Option<String>,
     last_name: Option<String>,
     address: Option<String>,
     city: Option<String>,
-    telephone: Option<String>,
+    email: Option<String>,
+    preferred_contact: ContactMethod,
+    emergency_contact_name: Option<String>,
+    emergency_contact_phone: Option<String>,
+    phones: Vec<owner_phone::Model>,
     pub pets_with_type: Vec<PetWithTypeAndVisits>,
+    /// owner 상세 화면에서만 조회하므로 with_balance로 채운 경우에만 Some임
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance: Option<OwnerBalance>,
+}
+
+impl OwnerWithPetsAndTypesAndVisits {
+    pub fn with_balance(self, balance: OwnerBalance) -> Self {
+        OwnerWithPetsAndTypesAndVisits {
+            balance: Some(balance),
+            ..self
+        }
+    }
+
+    /// 처치/처방을 visit별로 나눠 붙임
+    pub fn with_prescriptions(mut self, prescriptions: Vec<Prescription>) -> Self {
+        let mut by_visit: HashMap<i32, Vec<Prescription>> = HashMap::new();
+        for prescription in prescriptions {
+            by_visit
+                .entry(prescription.visit_id)
+                .or_default()
+                .push(prescription);
+        }
+
+        for visit in self
+            .pets_with_type
+            .iter_mut()
+            .flat_map(|pet| pet.visits.iter_mut())
+        {
+            visit.prescriptions = by_visit.remove(&visit.visit_id).unwrap_or_default();
+        }
+
+        self
+    }
+
+    /// 첨부 파일을 visit별로 나눠 붙임
+    pub fn with_attachments(mut self, attachments: Vec<visit_attachment::Model>) -> Self {
+        let mut by_visit: HashMap<i32, Vec<visit_attachment::Model>> = HashMap::new();
+        for attachment in attachments {
+            by_visit
+                .entry(attachment.visit_id)
+                .or_default()
+                .push(attachment);
+        }
+
+        for visit in self
+            .pets_with_type
+            .iter_mut()
+            .flat_map(|pet| pet.visits.iter_mut())
+        {
+            visit.attachments = by_visit.remove(&visit.visit_id).unwrap_or_default();
+        }
+
+        self
+    }
 }
 
 #[derive(Serialize)]
 pub struct PetWithTypeAndVisits {
-    pub pet_id: u32,
+    pub pet_id: i32,
     pub pet_name: Option<String>,
     pub birth_date: Option<Date>,
+    pub has_photo: bool,
     pub pet_type: PetType,
-    visits: Vec<Visit>,
+    pub visits: Vec<Visit>,
 }
 
 #[derive(Serialize)]
 pub struct PetType {
-    type_id: u32,
+    type_id: i32,
     pub type_name: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct Visit {
-    visit_id: u32,
-    visit_date: Option<Date>,
-    description: Option<String>,
+    pub visit_id: i32,
+    pub visit_date: Option<Date>,
+    pub description: Option<String>,
+    pub status: VisitStatus,
+    pub vet_id: Option<i32>,
+    pub vet_name: Option<String>,
+    pub starts_at: Option<DateTime>,
+    pub duration_minutes: Option<i32>,
+    pub room: Option<String>,
+    /// with_prescriptions로 채운 경우에만 비어 있지 않음
+    pub prescriptions: Vec<Prescription>,
+    /// with_attachments로 채운 경우에만 비어 있지 않음
+    pub attachments: Vec<visit_attachment::Model>,
 }
 
 #[derive(Serialize, FromQueryResult)]
 pub struct OwnersWithPetNames {
-    pub id: u32,
+    pub id: i32,
     first_name: Option<String>,
     last_name: Option<String>,
     address: Option<String>,
     city: Option<String>,
-    telephone: Option<String>,
+    email: Option<String>,
+    /// 처음 입력한 전화번호
+    phone: Option<String>,
     pet_names: Option<String>,
 }
 
 impl OwnerService {
+    #[instrument(skip(conn))]
     pub async fn fetch_owner_by_id(
         conn: &DbConn,
-        owner_id: u32,
+        owner_id: i32,
     ) -> Result<owners::Model, AppError> {
         owners::Entity::find_by_id(owner_id)
             .one(conn)
@@ -90,29 +198,42 @@ impl OwnerService {
             })
     }
 
+    #[instrument(skip(conn))]
     pub async fn fetch_owner_with_pets_and_types_and_visits_by_owner_id(
         conn: &DbConn,
-        owner_id: u32,
+        owner_id: i32,
     ) -> Result<OwnerWithPetsAndTypesAndVisits, AppError> {
         let rows = owners::Entity::find_by_id(owner_id)
             .join(JoinType::LeftJoin, owners::Relation::Pets.def())
             .join(JoinType::LeftJoin, pet::Relation::Types.def())
             .join(JoinType::LeftJoin, pet::Relation::Visits.def())
+            .join(JoinType::LeftJoin, visit::Relation::Vets.def())
             .select_only()
             .column_as(owners::Column::Id, "owner_id")
             .column(owners::Column::FirstName)
             .column(owners::Column::LastName)
             .column(owners::Column::Address)
             .column(owners::Column::City)
-            .column(owners::Column::Telephone)
+            .column(owners::Column::Email)
+            .column(owners::Column::PreferredContact)
+            .column(owners::Column::EmergencyContactName)
+            .column(owners::Column::EmergencyContactPhone)
             .column_as(pet::Column::Id, "pet_id")
             .column_as(pet::Column::Name, "pet_name")
             .column(pet::Column::BirthDate)
+            .column(pet::Column::ThumbnailKey)
             .column_as(types::Column::Id, "type_id")
             .column_as(types::Column::Name, "type_name")
             .column_as(visit::Column::Id, "visit_id")
             .column_as(visit::Column::VisitDate, "visit_date")
             .column_as(visit::Column::Description, "description")
+            .column_as(visit::Column::Status, "status")
+            .column_as(visit::Column::VetId, "vet_id")
+            .column_as(vet::Column::FirstName, "vet_first_name")
+            .column_as(vet::Column::LastName, "vet_last_name")
+            .column_as(visit::Column::StartsAt, "starts_at")
+            .column_as(visit::Column::DurationMinutes, "duration_minutes")
+            .column_as(visit::Column::Room, "room")
             .into_model::<OwnerWithPetsAndTypesAndVisitsQueryResult>()
             .all(conn)
             .await?;
@@ -124,7 +245,25 @@ impl OwnerService {
             });
         }
 
-        Ok(Self::transform_query_results(rows))
+        let mut owner = Self::transform_query_results(rows);
+        owner.phones = Self::fetch_phones_by_owner(conn, owner_id).await?;
+
+        Ok(owner)
+    }
+
+    /// 입력한 순서대로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_phones_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<Vec<owner_phone::Model>, AppError> {
+        let phones = owner_phone::Entity::find()
+            .filter(owner_phone::Column::OwnerId.eq(owner_id))
+            .order_by_asc(owner_phone::Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(phones)
     }
 
     fn transform_query_results(
@@ -140,8 +279,13 @@ impl OwnerService {
             last_name: first_row.last_name.clone(),
             address: first_row.address.clone(),
             city: first_row.city.clone(),
-            telephone: first_row.telephone.clone(),
+            email: first_row.email.clone(),
+            preferred_contact: first_row.preferred_contact,
+            emergency_contact_name: first_row.emergency_contact_name.clone(),
+            emergency_contact_phone: first_row.emergency_contact_phone.clone(),
+            phones: Vec::new(),
             pets_with_type,
+            balance: None,
         }
     }
 
@@ -152,7 +296,7 @@ impl OwnerService {
             .iter()
             .filter_map(|row| row.pet_id.map(|pet_id| (pet_id, row)))
             .fold(
-                HashMap::<u32, Vec<&OwnerWithPetsAndTypesAndVisitsQueryResult>>::new(),
+                HashMap::<i32, Vec<&OwnerWithPetsAndTypesAndVisitsQueryResult>>::new(),
                 |mut acc, (pet_id, row)| {
                     acc.entry(pet_id).or_default().push(row);
                     acc
@@ -168,7 +312,7 @@ impl OwnerService {
     }
 
     fn create_pet_with_type_and_visits(
-        pet_id: u32,
+        pet_id: i32,
         pet_rows: &[&OwnerWithPetsAndTypesAndVisitsQueryResult],
     ) -> PetWithTypeAndVisits {
         let first_pet_row = pet_rows.first().unwrap(); // 함수 실행 전 pet_id가 존재할 때만 실행되므로 반드시 Some임
@@ -179,16 +323,32 @@ impl OwnerService {
                     visit_id,
                     visit_date: r.visit_date,
                     description: r.description.clone(),
+                    // visit_id가 있으면 status 컬럼은 not null이므로 기본값이 쓰이지 않음
+                    status: r.status.unwrap_or(VisitStatus::Scheduled),
+                    vet_id: r.vet_id,
+                    vet_name: r.vet_id.map(|_| {
+                        format!(
+                            "{} {}",
+                            r.vet_first_name.as_deref().unwrap_or_default(),
+                            r.vet_last_name.as_deref().unwrap_or_default()
+                        )
+                    }),
+                    starts_at: r.starts_at,
+                    duration_minutes: r.duration_minutes,
+                    room: r.room.clone(),
+                    prescriptions: Vec::new(),
+                    attachments: Vec::new(),
                 })
             })
             .collect();
 
-        visits.sort_by_key(|v| v.visit_date);
+        visits.sort_by_key(|v| (v.visit_date, v.starts_at));
 
         PetWithTypeAndVisits {
             pet_id,
             pet_name: first_pet_row.pet_name.clone(),
             birth_date: first_pet_row.birth_date,
+            has_photo: first_pet_row.thumbnail_key.is_some(),
             pet_type: PetType {
                 type_id: first_pet_row.type_id.unwrap(),
                 type_name: first_pet_row.type_name.clone(),
@@ -197,28 +357,40 @@ impl OwnerService {
         }
     }
 
+    #[instrument(skip_all, fields(owner_id = field::Empty))]
     pub async fn save_owner(
         conn: &DbConn,
         first_name: Option<String>,
         last_name: Option<String>,
         address: Option<String>,
         city: Option<String>,
-        telephone: Option<String>,
+        contact: ContactDetails,
     ) -> Result<owners::Model, AppError> {
+        let txn = conn.begin().await?;
+
         let owner_active_model = owners::ActiveModel {
             first_name: ActiveValue::Set(first_name),
             last_name: ActiveValue::Set(last_name),
             address: ActiveValue::Set(address),
             city: ActiveValue::Set(city),
-            telephone: ActiveValue::Set(telephone),
+            email: ActiveValue::Set(contact.email),
+            preferred_contact: ActiveValue::Set(contact.preferred_contact),
+            emergency_contact_name: ActiveValue::Set(contact.emergency_contact_name),
+            emergency_contact_phone: ActiveValue::Set(contact.emergency_contact_phone),
             ..Default::default()
         };
 
-        let new_owner = owner_active_model.insert(conn).await?;
+        let new_owner = owner_active_model.insert(&txn).await?;
+        Self::replace_phones(&txn, new_owner.id, &contact.phones).await?;
+
+        txn.commit().await?;
+        Span::current().record("owner_id", new_owner.id);
+        METRICS.owners_created.inc();
 
         Ok(new_owner)
     }
 
+    #[instrument(skip(conn))]
     pub async fn fetch_owners_with_pet_names(
         conn: &DbConn,
         last_name: &str,
@@ -229,18 +401,35 @@ impl OwnerService {
             .left_join(pet::Entity)
             .filter(owners::Column::LastName.like(format!("{}%", last_name)))
             .column_as(
-                Expr::cust("GROUP_CONCAT(pets.name SEPARATOR ', ')"),
+                Self::pet_names_expr(conn.get_database_backend()),
                 "pet_names",
             )
+            .column_as(
+                Expr::cust(
+                    "(SELECT owner_phones.number FROM owner_phones \
+                     WHERE owner_phones.owner_id = owners.id \
+                     ORDER BY owner_phones.id LIMIT 1)",
+                ),
+                "phone",
+            )
             .group_by(owners::Column::Id)
             .into_model::<OwnersWithPetNames>()
             .paginate(conn, size)
-            .fetch_page(page - 1)
+            .fetch_page(page.saturating_sub(1))
             .await?;
 
         Ok(owners_with_pet_names)
     }
 
+    fn pet_names_expr(backend: DbBackend) -> SimpleExpr {
+        match backend {
+            DbBackend::MySql => Expr::cust("GROUP_CONCAT(pets.name SEPARATOR ', ')"),
+            DbBackend::Postgres => Expr::cust("STRING_AGG(pets.name, ', ')"),
+            DbBackend::Sqlite => Expr::cust("GROUP_CONCAT(pets.name, ', ')"),
+        }
+    }
+
+    #[instrument(skip(conn))]
     pub async fn fetch_owner_count_by_last_name_prefix(
         conn: &DbConn,
         last_name: &str,
@@ -253,26 +442,128 @@ impl OwnerService {
         Ok(owner_count)
     }
 
+    #[instrument(skip_all, fields(owner_id = owner_id))]
     pub async fn update_owner(
         conn: &DbConn,
-        owner_id: u32,
+        owner_id: i32,
         first_name: Option<String>,
         last_name: Option<String>,
         address: Option<String>,
         city: Option<String>,
-        telephone: Option<String>,
+        contact: ContactDetails,
     ) -> Result<owners::Model, AppError> {
+        let txn = conn.begin().await?;
+
         let owner_active_model = owners::ActiveModel {
             id: ActiveValue::Unchanged(owner_id),
             first_name: ActiveValue::Set(first_name),
             last_name: ActiveValue::Set(last_name),
             address: ActiveValue::Set(address),
             city: ActiveValue::Set(city),
-            telephone: ActiveValue::Set(telephone),
+            email: ActiveValue::Set(contact.email),
+            preferred_contact: ActiveValue::Set(contact.preferred_contact),
+            emergency_contact_name: ActiveValue::Set(contact.emergency_contact_name),
+            emergency_contact_phone: ActiveValue::Set(contact.emergency_contact_phone),
         };
 
-        let updated_owner = owner_active_model.update(conn).await?;
+        let updated_owner = owner_active_model.update(&txn).await?;
+        Self::replace_phones(&txn, owner_id, &contact.phones).await?;
+
+        txn.commit().await?;
 
         Ok(updated_owner)
     }
+
+    /// owner_phones는 form에서 입력한 번호로 통째로 교체함
+    async fn replace_phones(
+        txn: &DatabaseTransaction,
+        owner_id: i32,
+        phones: &[(PhoneKind, String)],
+    ) -> Result<(), AppError> {
+        owner_phone::Entity::delete_many()
+            .filter(owner_phone::Column::OwnerId.eq(owner_id))
+            .exec(txn)
+            .await?;
+
+        if !phones.is_empty() {
+            let owner_phones = phones
+                .iter()
+                .map(|(kind, number)| owner_phone::ActiveModel {
+                    owner_id: ActiveValue::Set(owner_id),
+                    kind: ActiveValue::Set(*kind),
+                    number: ActiveValue::Set(number.clone()),
+                    ..Default::default()
+                });
+            owner_phone::Entity::insert_many(owner_phones)
+                .exec(txn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// pet이나 invoice가 남아 있는 owner는 삭제하지 않음. pet(과 visit)을 먼저 삭제해야 owner를 삭제할 수 있음
+    #[instrument(skip(conn))]
+    pub async fn delete_owner(conn: &DbConn, owner_id: i32) -> Result<(), AppError> {
+        let txn = conn.begin().await?;
+
+        let pet_count = pet::Entity::find()
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .count(&txn)
+            .await?;
+        if pet_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "owner".to_string(),
+                id: owner_id,
+                dependents: format!("{pet_count} pet(s)"),
+            });
+        }
+
+        // invoice와 결제 기록은 owner가 있어야 조회할 수 있으므로 남아 있으면 삭제하지 않음
+        let invoice_count = invoice::Entity::find()
+            .filter(invoice::Column::OwnerId.eq(owner_id))
+            .count(&txn)
+            .await?;
+        if invoice_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "owner".to_string(),
+                id: owner_id,
+                dependents: format!("{invoice_count} invoice(s)"),
+            });
+        }
+
+        // 넘겨준 pet의 이전 기록은 남기고 삭제되는 owner에 대한 참조만 지움
+        pet_transfer::Entity::update_many()
+            .col_expr(
+                pet_transfer::Column::FromOwnerId,
+                Expr::value(Option::<i32>::None),
+            )
+            .filter(pet_transfer::Column::FromOwnerId.eq(owner_id))
+            .exec(&txn)
+            .await?;
+        pet_transfer::Entity::update_many()
+            .col_expr(
+                pet_transfer::Column::ToOwnerId,
+                Expr::value(Option::<i32>::None),
+            )
+            .filter(pet_transfer::Column::ToOwnerId.eq(owner_id))
+            .exec(&txn)
+            .await?;
+
+        owner_phone::Entity::delete_many()
+            .filter(owner_phone::Column::OwnerId.eq(owner_id))
+            .exec(&txn)
+            .await?;
+        let result = owners::Entity::delete_by_id(owner_id).exec(&txn).await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::ResourceNotFound {
+                resource: "owner".to_string(),
+                id: owner_id,
+            });
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
 }
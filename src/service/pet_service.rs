@@ -1,25 +1,67 @@
-use sea_orm::{prelude::Date, ActiveModelTrait, ActiveValue, DbConn, EntityTrait};
+use chrono::Local;
+use sea_orm::{
+    prelude::{Date, Expr},
+    sea_query::{Alias, Condition, Func},
+    ActiveModelTrait, ActiveValue, ColumnTrait, DbConn, EntityTrait, FromQueryResult, JoinType,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, warn, Span};
+use uuid::Uuid;
 
 use crate::{
-    domain::owner::{pet, types},
-    model::app_error::AppError,
+    domain::owner::{billable_item, owners, pet, pet_transfer, prescription, vaccination, visit},
+    model::{app_error::AppError, photo::ProcessedPhoto},
+    service::attachment_service::AttachmentService,
+    storage::Storage,
 };
 
 pub struct PetService;
 
-impl PetService {
-    pub async fn fetch_all_pet_types(conn: &DbConn) -> Result<Vec<types::Model>, AppError> {
-        let pet_types = types::Entity::find().all(conn).await?;
+#[derive(Serialize, FromQueryResult)]
+pub struct PetTransferHistory {
+    transferred_on: Date,
+    pet_id: i32,
+    pet_name: Option<String>,
+    from_owner_id: Option<i32>,
+    from_owner_first_name: Option<String>,
+    from_owner_last_name: Option<String>,
+    to_owner_id: Option<i32>,
+    to_owner_first_name: Option<String>,
+    to_owner_last_name: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum PhotoSize {
+    Full,
+    Thumbnail,
+}
 
-        Ok(pet_types)
+impl PetService {
+    /// 다른 owner의 pet id로 조회하지 않도록 owner까지 확인함
+    #[instrument(skip(conn))]
+    pub async fn fetch_pet(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+    ) -> Result<pet::Model, AppError> {
+        pet::Entity::find_by_id(pet_id)
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet".to_string(),
+                id: pet_id,
+            })
     }
 
+    #[instrument(skip_all, fields(owner_id = owner_id, pet_id = field::Empty))]
     pub async fn save_pet(
         conn: &DbConn,
         name: Option<String>,
         birth_date: Option<Date>,
-        type_id: u32,
-        owner_id: Option<u32>,
+        type_id: i32,
+        owner_id: Option<i32>,
     ) -> Result<pet::Model, AppError> {
         let pet_active_model = pet::ActiveModel {
             name: ActiveValue::Set(name),
@@ -30,16 +72,18 @@ impl PetService {
         };
 
         let new_pet = pet_active_model.insert(conn).await?;
+        Span::current().record("pet_id", new_pet.id);
 
         Ok(new_pet)
     }
 
+    #[instrument(skip_all, fields(pet_id = pet_id))]
     pub async fn update_pet(
         conn: &DbConn,
-        pet_id: u32,
+        pet_id: i32,
         name: Option<String>,
         birth_date: Option<Date>,
-        type_id: u32,
+        type_id: i32,
     ) -> Result<pet::Model, AppError> {
         let pet_active_model = pet::ActiveModel {
             id: ActiveValue::Unchanged(pet_id),
@@ -53,4 +97,406 @@ impl PetService {
 
         Ok(new_pet)
     }
+
+    /// pet과 그 visit을 다른 owner에게 옮기고 이전 기록을 pet_transfers에 남김.
+    /// visit은 pet을 참조하므로 pet의 owner만 바꾸면 함께 옮겨짐.
+    /// 청구하지 않은 항목은 pet의 owner 기준으로 청구하므로, 남아 있으면 새 owner에게 청구되지 않도록 옮기지 않음
+    #[instrument(skip(conn))]
+    pub async fn transfer_pet(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        new_owner_id: i32,
+    ) -> Result<pet_transfer::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let pet = pet::Entity::find_by_id(pet_id)
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet".to_string(),
+                id: pet_id,
+            })?;
+
+        if new_owner_id == owner_id {
+            return Err(AppError::InvalidResourceState {
+                resource: "pet".to_string(),
+                id: pet_id,
+                state: format!("already owned by owner {owner_id}"),
+            });
+        }
+
+        owners::Entity::find_by_id(new_owner_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "owner".to_string(),
+                id: new_owner_id,
+            })?;
+
+        let unbilled_count = billable_item::Entity::find()
+            .inner_join(visit::Entity)
+            .filter(visit::Column::PetId.eq(pet_id))
+            .filter(billable_item::Column::InvoiceId.is_null())
+            .count(&txn)
+            .await?;
+        if unbilled_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "pet".to_string(),
+                id: pet_id,
+                dependents: format!("{unbilled_count} unbilled item(s)"),
+            });
+        }
+
+        // 한 owner 안에서 pet 이름은 중복될 수 없음
+        let pet_name = pet.name.clone().unwrap_or_default();
+        let duplicate_count = pet::Entity::find()
+            .filter(pet::Column::OwnerId.eq(new_owner_id))
+            .filter(
+                Expr::expr(Func::lower(Expr::col(pet::Column::Name))).eq(pet_name.to_lowercase()),
+            )
+            .count(&txn)
+            .await?;
+        if duplicate_count > 0 {
+            return Err(AppError::DuplicateResource {
+                resource: "pet".to_string(),
+                field: "name".to_string(),
+                value: pet_name,
+            });
+        }
+
+        let mut pet_active_model: pet::ActiveModel = pet.into();
+        pet_active_model.owner_id = ActiveValue::Set(Some(new_owner_id));
+        pet_active_model.update(&txn).await?;
+
+        let transfer = pet_transfer::ActiveModel {
+            pet_id: ActiveValue::Set(pet_id),
+            from_owner_id: ActiveValue::Set(Some(owner_id)),
+            to_owner_id: ActiveValue::Set(Some(new_owner_id)),
+            transferred_on: ActiveValue::Set(Local::now().date_naive()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(transfer)
+    }
+
+    /// owner가 넘겨주거나 넘겨받은 pet의 이전 기록을 최신순으로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_transfers_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<Vec<PetTransferHistory>, AppError> {
+        let (from_owner, to_owner) = (Alias::new("from_owner"), Alias::new("to_owner"));
+
+        let transfers = pet_transfer::Entity::find()
+            .join(JoinType::InnerJoin, pet_transfer::Relation::Pets.def())
+            .join_as(
+                JoinType::LeftJoin,
+                pet_transfer::Relation::FromOwner.def(),
+                from_owner.clone(),
+            )
+            .join_as(
+                JoinType::LeftJoin,
+                pet_transfer::Relation::ToOwner.def(),
+                to_owner.clone(),
+            )
+            .filter(
+                Condition::any()
+                    .add(pet_transfer::Column::FromOwnerId.eq(owner_id))
+                    .add(pet_transfer::Column::ToOwnerId.eq(owner_id)),
+            )
+            .select_only()
+            .column(pet_transfer::Column::TransferredOn)
+            .column(pet_transfer::Column::PetId)
+            .column_as(pet::Column::Name, "pet_name")
+            .column(pet_transfer::Column::FromOwnerId)
+            .column_as(
+                Expr::col((from_owner.clone(), owners::Column::FirstName)),
+                "from_owner_first_name",
+            )
+            .column_as(
+                Expr::col((from_owner, owners::Column::LastName)),
+                "from_owner_last_name",
+            )
+            .column(pet_transfer::Column::ToOwnerId)
+            .column_as(
+                Expr::col((to_owner.clone(), owners::Column::FirstName)),
+                "to_owner_first_name",
+            )
+            .column_as(
+                Expr::col((to_owner, owners::Column::LastName)),
+                "to_owner_last_name",
+            )
+            .order_by_desc(pet_transfer::Column::TransferredOn)
+            .order_by_desc(pet_transfer::Column::Id)
+            .into_model::<PetTransferHistory>()
+            .all(conn)
+            .await?;
+
+        Ok(transfers)
+    }
+
+    /// 새 사진을 저장소에 먼저 저장한 뒤 pet이 가리키는 key를 바꾸고 이전 사진을 지움.
+    /// key마다 새 이름을 쓰므로 저장 중에 실패해도 이전 사진은 그대로 남음
+    #[instrument(skip(conn, storage, photo))]
+    pub async fn replace_photo(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+        photo: ProcessedPhoto,
+    ) -> Result<(), AppError> {
+        let pet = Self::fetch_pet(conn, owner_id, pet_id).await?;
+
+        let name = Uuid::new_v4();
+        let photo_key = format!("pets/{pet_id}/{name}.{}", photo.extension);
+        let thumbnail_key = format!("pets/{pet_id}/{name}-thumbnail.{}", photo.extension);
+        storage.put(&photo_key, &photo.image).await?;
+        storage.put(&thumbnail_key, &photo.thumbnail).await?;
+
+        let previous_keys = [pet.photo_key.clone(), pet.thumbnail_key.clone()];
+        if let Err(e) = Self::update_photo_keys(
+            conn,
+            pet,
+            Some(photo_key.clone()),
+            Some(thumbnail_key.clone()),
+        )
+        .await
+        {
+            Self::delete_photo_files(storage, &[Some(photo_key), Some(thumbnail_key)]).await;
+            return Err(e);
+        }
+        Self::delete_photo_files(storage, &previous_keys).await;
+
+        Ok(())
+    }
+
+    #[instrument(skip(conn, storage))]
+    pub async fn remove_photo(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+    ) -> Result<(), AppError> {
+        let pet = Self::fetch_pet(conn, owner_id, pet_id).await?;
+
+        let previous_keys = [pet.photo_key.clone(), pet.thumbnail_key.clone()];
+        Self::update_photo_keys(conn, pet, None, None).await?;
+        Self::delete_photo_files(storage, &previous_keys).await;
+
+        Ok(())
+    }
+
+    async fn update_photo_keys(
+        conn: &DbConn,
+        pet: pet::Model,
+        photo_key: Option<String>,
+        thumbnail_key: Option<String>,
+    ) -> Result<(), AppError> {
+        let mut pet_active_model: pet::ActiveModel = pet.into();
+        pet_active_model.photo_key = ActiveValue::Set(photo_key);
+        pet_active_model.thumbnail_key = ActiveValue::Set(thumbnail_key);
+        pet_active_model.update(conn).await?;
+
+        Ok(())
+    }
+
+    /// 남은 파일은 공간만 차지할 뿐 화면에는 보이지 않으므로 삭제에 실패해도 log만 남김
+    async fn delete_photo_files(storage: &dyn Storage, keys: &[Option<String>]) {
+        for key in keys.iter().flatten() {
+            if let Err(e) = storage.delete(key).await {
+                warn!(key = key.as_str(), error = %e, "failed to delete photo file");
+            }
+        }
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_photo_key(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        size: PhotoSize,
+    ) -> Result<String, AppError> {
+        let pet = Self::fetch_pet(conn, owner_id, pet_id).await?;
+
+        let key = match size {
+            PhotoSize::Full => pet.photo_key,
+            PhotoSize::Thumbnail => pet.thumbnail_key,
+        };
+
+        key.ok_or_else(|| AppError::ResourceNotFound {
+            resource: "photo".to_string(),
+            id: pet_id,
+        })
+    }
+
+    #[instrument(skip(storage))]
+    pub async fn read_photo(
+        storage: &dyn Storage,
+        pet_id: i32,
+        key: &str,
+    ) -> Result<Vec<u8>, AppError> {
+        storage.get(key).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::ResourceNotFound {
+                resource: "photo".to_string(),
+                id: pet_id,
+            },
+            _ => AppError::StorageError(e),
+        })
+    }
+
+    /// pet과 그 visit, 청구하지 않은 항목, 첨부 파일, 이전 기록, 접종 기록을 한 transaction에서 삭제하고,
+    /// 함께 삭제된 visit 수를 반환함. 사진과 첨부 파일은 transaction이 끝난 뒤 지움
+    #[instrument(skip(conn, storage))]
+    pub async fn delete_pet(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+    ) -> Result<u64, AppError> {
+        let txn = conn.begin().await?;
+
+        // 다른 owner의 pet id로 삭제하지 않도록 owner까지 확인함
+        let pet = pet::Entity::find_by_id(pet_id)
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet".to_string(),
+                id: pet_id,
+            })?;
+
+        // 청구한 항목이 있는 visit은 invoice에 남아 있어야 하므로 pet을 삭제하지 않음
+        let visit_ids = visit::Entity::find()
+            .select_only()
+            .column(visit::Column::Id)
+            .filter(visit::Column::PetId.eq(pet_id))
+            .into_tuple::<i32>()
+            .all(&txn)
+            .await?;
+        let billed_count = billable_item::Entity::find()
+            .filter(billable_item::Column::VisitId.is_in(visit_ids.clone()))
+            .filter(billable_item::Column::InvoiceId.is_not_null())
+            .count(&txn)
+            .await?;
+        if billed_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "pet".to_string(),
+                id: pet_id,
+                dependents: format!("{billed_count} billed item(s)"),
+            });
+        }
+
+        billable_item::Entity::delete_many()
+            .filter(billable_item::Column::VisitId.is_in(visit_ids.clone()))
+            .exec(&txn)
+            .await?;
+        prescription::Entity::delete_many()
+            .filter(prescription::Column::VisitId.is_in(visit_ids.clone()))
+            .exec(&txn)
+            .await?;
+        let attachment_keys =
+            AttachmentService::delete_attachments_of_visits(&txn, &visit_ids).await?;
+        let deleted_visits = visit::Entity::delete_many()
+            .filter(visit::Column::PetId.eq(pet_id))
+            .exec(&txn)
+            .await?;
+        pet_transfer::Entity::delete_many()
+            .filter(pet_transfer::Column::PetId.eq(pet_id))
+            .exec(&txn)
+            .await?;
+        vaccination::Entity::delete_many()
+            .filter(vaccination::Column::PetId.eq(pet_id))
+            .exec(&txn)
+            .await?;
+        pet::Entity::delete_by_id(pet_id).exec(&txn).await?;
+
+        txn.commit().await?;
+
+        Self::delete_photo_files(storage, &[pet.photo_key, pet.thumbnail_key]).await;
+        AttachmentService::delete_files(storage, &attachment_keys).await;
+
+        Ok(deleted_visits.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::db::connect_test_db,
+        domain::owner::billable_item::BillableItemKind,
+        model::money::Money,
+        service::{
+            billing_service::{BillingService, NewBillableItem},
+            visit_service::VisitService,
+        },
+    };
+
+    #[actix_web::test]
+    async fn test_transfer_pet_blocked_by_unbilled_items() {
+        let conn = connect_test_db().await;
+
+        // pet 7은 owner 6의 pet이며 visit 4가 있음
+        BillingService::add_item(
+            &conn,
+            6,
+            7,
+            4,
+            NewBillableItem {
+                kind: BillableItemKind::Service,
+                description: "Spay".to_string(),
+                quantity: 1,
+                unit_price: Money::from_cents(12000),
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = PetService::transfer_pet(&conn, 6, 7, 1).await;
+        assert!(matches!(result, Err(AppError::ResourceInUse { .. })));
+
+        BillingService::create_invoice(&conn, 6, 0).await.unwrap();
+        PetService::transfer_pet(&conn, 6, 7, 1).await.unwrap();
+        let pet = PetService::fetch_pet(&conn, 1, 7).await.unwrap();
+        assert_eq!(pet.owner_id, Some(1));
+    }
+
+    #[actix_web::test]
+    async fn test_transfer_pet_records_history() {
+        let conn = connect_test_db().await;
+
+        // pet 8은 owner 6의 pet이며 visit 2, 3이 있음
+        let result = PetService::transfer_pet(&conn, 1, 8, 2).await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+        let result = PetService::transfer_pet(&conn, 6, 8, 6).await;
+        assert!(matches!(result, Err(AppError::InvalidResourceState { .. })));
+        // owner 7에게는 이미 Lucky라는 pet이 있음
+        let result = PetService::transfer_pet(&conn, 10, 12, 7).await;
+        assert!(matches!(result, Err(AppError::DuplicateResource { .. })));
+
+        PetService::transfer_pet(&conn, 6, 8, 1).await.unwrap();
+        VisitService::fetch_visit_of_pet(&conn, 1, 8, 2)
+            .await
+            .unwrap();
+
+        for owner_id in [6, 1] {
+            let transfers = PetService::fetch_transfers_by_owner(&conn, owner_id)
+                .await
+                .unwrap();
+            assert_eq!(transfers.len(), 1);
+            assert_eq!(transfers[0].pet_id, 8);
+            assert_eq!(transfers[0].from_owner_id, Some(6));
+            assert_eq!(transfers[0].to_owner_id, Some(1));
+        }
+        let transfers = PetService::fetch_transfers_by_owner(&conn, 10)
+            .await
+            .unwrap();
+        assert!(transfers.is_empty());
+    }
 }
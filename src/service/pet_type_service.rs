@@ -0,0 +1,227 @@
+use sea_orm::{
+    prelude::Expr, sea_query::Func, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait,
+    DbConn, EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, TransactionTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, Span};
+
+use crate::{
+    domain::owner::{pet, types},
+    model::app_error::AppError,
+};
+
+pub struct PetTypeService;
+
+#[derive(Serialize, FromQueryResult)]
+pub struct PetTypeWithPetCount {
+    pub id: i32,
+    pub name: Option<String>,
+    pub pet_count: i64,
+}
+
+impl PetTypeService {
+    #[instrument(skip(conn))]
+    pub async fn fetch_all_pet_types(conn: &DbConn) -> Result<Vec<types::Model>, AppError> {
+        let pet_types = types::Entity::find()
+            .order_by_asc(types::Column::Name)
+            .all(conn)
+            .await?;
+
+        Ok(pet_types)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_pet_types_with_pet_count(
+        conn: &DbConn,
+    ) -> Result<Vec<PetTypeWithPetCount>, AppError> {
+        let pet_types = types::Entity::find()
+            .join(JoinType::LeftJoin, types::Relation::Pets.def())
+            .select_only()
+            .column(types::Column::Id)
+            .column(types::Column::Name)
+            .column_as(pet::Column::Id.count(), "pet_count")
+            .group_by(types::Column::Id)
+            .group_by(types::Column::Name)
+            .order_by_asc(types::Column::Name)
+            .into_model::<PetTypeWithPetCount>()
+            .all(conn)
+            .await?;
+
+        Ok(pet_types)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_pet_type_by_id(
+        conn: &DbConn,
+        pet_type_id: i32,
+    ) -> Result<types::Model, AppError> {
+        types::Entity::find_by_id(pet_type_id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet type".to_string(),
+                id: pet_type_id,
+            })
+    }
+
+    /// form에서 고른 이름으로 pet type id를 찾음. 없는 이름이면 None임
+    #[instrument(skip(conn))]
+    pub async fn fetch_pet_type_id_by_name(
+        conn: &DbConn,
+        name: &str,
+    ) -> Result<Option<i32>, AppError> {
+        let pet_type_id = types::Entity::find()
+            .filter(types::Column::Name.eq(name))
+            .select_only()
+            .column(types::Column::Id)
+            .into_tuple::<i32>()
+            .one(conn)
+            .await?;
+
+        Ok(pet_type_id)
+    }
+
+    #[instrument(skip_all, fields(pet_type_id = field::Empty))]
+    pub async fn save_pet_type(conn: &DbConn, name: &str) -> Result<types::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let name = name.trim();
+        Self::ensure_name_is_unique(&txn, name, None).await?;
+
+        let pet_type_active_model = types::ActiveModel {
+            name: ActiveValue::Set(Some(name.to_string())),
+            ..Default::default()
+        };
+        let new_pet_type = pet_type_active_model.insert(&txn).await?;
+        Span::current().record("pet_type_id", new_pet_type.id);
+
+        txn.commit().await?;
+
+        Ok(new_pet_type)
+    }
+
+    #[instrument(skip_all, fields(pet_type_id = pet_type_id))]
+    pub async fn update_pet_type(
+        conn: &DbConn,
+        pet_type_id: i32,
+        name: &str,
+    ) -> Result<types::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let mut pet_type_active_model: types::ActiveModel = types::Entity::find_by_id(pet_type_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet type".to_string(),
+                id: pet_type_id,
+            })?
+            .into();
+
+        let name = name.trim();
+        Self::ensure_name_is_unique(&txn, name, Some(pet_type_id)).await?;
+
+        pet_type_active_model.name = ActiveValue::Set(Some(name.to_string()));
+        let updated_pet_type = pet_type_active_model.update(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(updated_pet_type)
+    }
+
+    /// 대소문자만 다른 이름도 중복으로 봄
+    async fn ensure_name_is_unique<C: ConnectionTrait>(
+        conn: &C,
+        name: &str,
+        exclude_pet_type_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let mut duplicate_query = types::Entity::find().filter(
+            Expr::expr(Func::lower(Expr::col(types::Column::Name))).eq(name.to_lowercase()),
+        );
+        if let Some(pet_type_id) = exclude_pet_type_id {
+            duplicate_query = duplicate_query.filter(types::Column::Id.ne(pet_type_id));
+        }
+
+        if duplicate_query.count(conn).await? > 0 {
+            return Err(AppError::DuplicateResource {
+                resource: "pet type".to_string(),
+                field: "name".to_string(),
+                value: name.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// 해당 type의 pet이 남아 있으면 삭제하지 않음
+    #[instrument(skip(conn))]
+    pub async fn delete_pet_type(conn: &DbConn, pet_type_id: i32) -> Result<(), AppError> {
+        let txn = conn.begin().await?;
+
+        let pet_count = pet::Entity::find()
+            .filter(pet::Column::TypeId.eq(pet_type_id))
+            .count(&txn)
+            .await?;
+        if pet_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "pet type".to_string(),
+                id: pet_type_id,
+                dependents: format!("{pet_count} pet(s)"),
+            });
+        }
+
+        let result = types::Entity::delete_by_id(pet_type_id).exec(&txn).await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::ResourceNotFound {
+                resource: "pet type".to_string(),
+                id: pet_type_id,
+            });
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::db::connect_test_db;
+
+    #[actix_web::test]
+    async fn test_new_pet_type_is_found_by_name() {
+        let conn = connect_test_db().await;
+
+        let result = PetTypeService::save_pet_type(&conn, "Dog").await;
+        assert!(matches!(result, Err(AppError::DuplicateResource { .. })));
+
+        let pet_type = PetTypeService::save_pet_type(&conn, " rabbit ")
+            .await
+            .unwrap();
+        let pet_type_id = PetTypeService::fetch_pet_type_id_by_name(&conn, "rabbit")
+            .await
+            .unwrap();
+        assert_eq!(pet_type_id, Some(pet_type.id));
+    }
+
+    #[actix_web::test]
+    async fn test_delete_pet_type_in_use() {
+        let conn = connect_test_db().await;
+
+        // cat은 여러 pet이 사용 중임
+        let result = PetTypeService::delete_pet_type(&conn, 1).await;
+        assert!(matches!(result, Err(AppError::ResourceInUse { .. })));
+
+        let pet_type = PetTypeService::save_pet_type(&conn, "rabbit")
+            .await
+            .unwrap();
+        PetTypeService::delete_pet_type(&conn, pet_type.id)
+            .await
+            .unwrap();
+        let pet_type_id = PetTypeService::fetch_pet_type_id_by_name(&conn, "rabbit")
+            .await
+            .unwrap();
+        assert_eq!(pet_type_id, None);
+    }
+}
@@ -0,0 +1,213 @@
+use sea_orm::{
+    prelude::Expr, sea_query::Func, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait,
+    DbConn, EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, RelationTrait, TransactionTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, Span};
+
+use crate::{
+    domain::veterinarian::{specialty, vet_specialty},
+    model::app_error::AppError,
+};
+
+pub struct SpecialtyService;
+
+#[derive(Serialize, FromQueryResult)]
+pub struct SpecialtyWithVetCount {
+    pub id: i32,
+    pub name: Option<String>,
+    pub vet_count: i64,
+}
+
+impl SpecialtyService {
+    #[instrument(skip(conn))]
+    pub async fn fetch_all_specialties(conn: &DbConn) -> Result<Vec<specialty::Model>, AppError> {
+        let specialties = specialty::Entity::find()
+            .order_by_asc(specialty::Column::Name)
+            .all(conn)
+            .await?;
+
+        Ok(specialties)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_specialties_with_vet_count(
+        conn: &DbConn,
+    ) -> Result<Vec<SpecialtyWithVetCount>, AppError> {
+        let specialties = specialty::Entity::find()
+            .join(
+                JoinType::LeftJoin,
+                specialty::Relation::VetSpecialties.def(),
+            )
+            .select_only()
+            .column(specialty::Column::Id)
+            .column(specialty::Column::Name)
+            .column_as(vet_specialty::Column::VetId.count(), "vet_count")
+            .group_by(specialty::Column::Id)
+            .group_by(specialty::Column::Name)
+            .order_by_asc(specialty::Column::Name)
+            .into_model::<SpecialtyWithVetCount>()
+            .all(conn)
+            .await?;
+
+        Ok(specialties)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_specialty_by_id(
+        conn: &DbConn,
+        specialty_id: i32,
+    ) -> Result<specialty::Model, AppError> {
+        specialty::Entity::find_by_id(specialty_id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "specialty".to_string(),
+                id: specialty_id,
+            })
+    }
+
+    #[instrument(skip_all, fields(specialty_id = field::Empty))]
+    pub async fn save_specialty(conn: &DbConn, name: &str) -> Result<specialty::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let name = name.trim();
+        Self::ensure_name_is_unique(&txn, name, None).await?;
+
+        let specialty_active_model = specialty::ActiveModel {
+            name: ActiveValue::Set(Some(name.to_string())),
+            ..Default::default()
+        };
+        let new_specialty = specialty_active_model.insert(&txn).await?;
+        Span::current().record("specialty_id", new_specialty.id);
+
+        txn.commit().await?;
+
+        Ok(new_specialty)
+    }
+
+    #[instrument(skip_all, fields(specialty_id = specialty_id))]
+    pub async fn update_specialty(
+        conn: &DbConn,
+        specialty_id: i32,
+        name: &str,
+    ) -> Result<specialty::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let mut specialty_active_model: specialty::ActiveModel =
+            specialty::Entity::find_by_id(specialty_id)
+                .one(&txn)
+                .await?
+                .ok_or_else(|| AppError::ResourceNotFound {
+                    resource: "specialty".to_string(),
+                    id: specialty_id,
+                })?
+                .into();
+
+        let name = name.trim();
+        Self::ensure_name_is_unique(&txn, name, Some(specialty_id)).await?;
+
+        specialty_active_model.name = ActiveValue::Set(Some(name.to_string()));
+        let updated_specialty = specialty_active_model.update(&txn).await?;
+
+        txn.commit().await?;
+
+        Ok(updated_specialty)
+    }
+
+    /// 대소문자만 다른 이름도 중복으로 봄
+    async fn ensure_name_is_unique<C: ConnectionTrait>(
+        conn: &C,
+        name: &str,
+        exclude_specialty_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let mut duplicate_query = specialty::Entity::find().filter(
+            Expr::expr(Func::lower(Expr::col(specialty::Column::Name))).eq(name.to_lowercase()),
+        );
+        if let Some(specialty_id) = exclude_specialty_id {
+            duplicate_query = duplicate_query.filter(specialty::Column::Id.ne(specialty_id));
+        }
+
+        if duplicate_query.count(conn).await? > 0 {
+            return Err(AppError::DuplicateResource {
+                resource: "specialty".to_string(),
+                field: "name".to_string(),
+                value: name.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// vet이 아직 가진 specialty는 삭제하지 않음. vet 편집 화면에서 먼저 빼야 함
+    #[instrument(skip(conn))]
+    pub async fn delete_specialty(conn: &DbConn, specialty_id: i32) -> Result<(), AppError> {
+        let txn = conn.begin().await?;
+
+        let vet_count = vet_specialty::Entity::find()
+            .filter(vet_specialty::Column::SpecialtyId.eq(specialty_id))
+            .count(&txn)
+            .await?;
+        if vet_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "specialty".to_string(),
+                id: specialty_id,
+                dependents: format!("{vet_count} vet(s)"),
+            });
+        }
+
+        let result = specialty::Entity::delete_by_id(specialty_id)
+            .exec(&txn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::ResourceNotFound {
+                resource: "specialty".to_string(),
+                id: specialty_id,
+            });
+        }
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::db::connect_test_db;
+
+    #[actix_web::test]
+    async fn test_specialty_name_must_be_unique() {
+        let conn = connect_test_db().await;
+
+        let result = SpecialtyService::save_specialty(&conn, " Radiology ").await;
+        assert!(matches!(result, Err(AppError::DuplicateResource { .. })));
+
+        // 자기 이름으로 다시 저장하는 것은 중복이 아님
+        SpecialtyService::update_specialty(&conn, 1, "Radiology")
+            .await
+            .unwrap();
+        let result = SpecialtyService::update_specialty(&conn, 2, "radiology").await;
+        assert!(matches!(result, Err(AppError::DuplicateResource { .. })));
+    }
+
+    #[actix_web::test]
+    async fn test_delete_specialty_in_use() {
+        let conn = connect_test_db().await;
+
+        // radiology는 vet 2와 5가 가지고 있음
+        let result = SpecialtyService::delete_specialty(&conn, 1).await;
+        assert!(matches!(result, Err(AppError::ResourceInUse { .. })));
+
+        let specialty = SpecialtyService::save_specialty(&conn, "oncology")
+            .await
+            .unwrap();
+        SpecialtyService::delete_specialty(&conn, specialty.id)
+            .await
+            .unwrap();
+        let result = SpecialtyService::fetch_specialty_by_id(&conn, specialty.id).await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+    }
+}
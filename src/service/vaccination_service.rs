@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use chrono::{Days, Local};
+use sea_orm::{
+    prelude::Date, ActiveModelTrait, ActiveValue, ColumnTrait, DbConn, EntityTrait,
+    FromQueryResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, Span};
+
+use crate::{
+    domain::{
+        owner::{owners, pet, vaccination},
+        veterinarian::vet,
+    },
+    model::app_error::AppError,
+};
+
+pub struct VaccinationService;
+
+/// 새로 기록할 접종 정보
+#[derive(Clone, Debug)]
+pub struct NewVaccination {
+    pub vaccine: String,
+    pub given_on: Date,
+    pub lot_number: Option<String>,
+    pub vet_id: Option<i32>,
+    pub next_due_on: Option<Date>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DueStatus {
+    Overdue,
+    DueSoon,
+}
+
+#[derive(FromQueryResult)]
+struct VaccinationRow {
+    id: i32,
+    pet_id: i32,
+    vaccine: String,
+    given_on: Date,
+    lot_number: Option<String>,
+    next_due_on: Option<Date>,
+    vet_first_name: Option<String>,
+    vet_last_name: Option<String>,
+}
+
+/// owner 상세 화면에 표시할 접종 기록.
+/// 같은 pet의 같은 백신을 다시 접종한 경우 이전 기록은 `due_status`가 없음
+#[derive(Serialize)]
+pub struct Vaccination {
+    id: i32,
+    pet_id: i32,
+    vaccine: String,
+    given_on: Date,
+    lot_number: Option<String>,
+    next_due_on: Option<Date>,
+    vet_name: Option<String>,
+    due_status: Option<DueStatus>,
+}
+
+#[derive(FromQueryResult)]
+struct DueVaccinationRow {
+    id: i32,
+    pet_id: i32,
+    vaccine: String,
+    next_due_on: Date,
+    pet_name: Option<String>,
+    owner_id: i32,
+    owner_first_name: Option<String>,
+    owner_last_name: Option<String>,
+}
+
+/// 접종 예정 보고서의 한 줄
+#[derive(Serialize)]
+pub struct DueVaccination {
+    pet_id: i32,
+    pet_name: Option<String>,
+    owner_id: i32,
+    owner_first_name: Option<String>,
+    owner_last_name: Option<String>,
+    vaccine: String,
+    next_due_on: Date,
+    due_status: Option<DueStatus>,
+}
+
+impl VaccinationService {
+    /// pet이 path의 owner에 속할 때만 접종 기록을 저장함
+    #[instrument(skip_all, fields(owner_id = owner_id, pet_id = pet_id, vaccination_id = field::Empty))]
+    pub async fn save_vaccination(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        new_vaccination: NewVaccination,
+    ) -> Result<vaccination::Model, AppError> {
+        pet::Entity::find_by_id(pet_id)
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "pet".to_string(),
+                id: pet_id,
+            })?;
+
+        let vaccination = vaccination::ActiveModel {
+            pet_id: ActiveValue::Set(pet_id),
+            vaccine: ActiveValue::Set(new_vaccination.vaccine),
+            given_on: ActiveValue::Set(new_vaccination.given_on),
+            lot_number: ActiveValue::Set(new_vaccination.lot_number),
+            vet_id: ActiveValue::Set(new_vaccination.vet_id),
+            next_due_on: ActiveValue::Set(new_vaccination.next_due_on),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+        Span::current().record("vaccination_id", vaccination.id);
+
+        Ok(vaccination)
+    }
+
+    /// owner의 모든 pet의 접종 기록을 최신순으로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_vaccinations_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+        due_soon_days: u32,
+    ) -> Result<Vec<Vaccination>, AppError> {
+        let rows = vaccination::Entity::find()
+            .join(JoinType::InnerJoin, vaccination::Relation::Pets.def())
+            .join(JoinType::LeftJoin, vaccination::Relation::Vets.def())
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .select_only()
+            .column(vaccination::Column::Id)
+            .column(vaccination::Column::PetId)
+            .column(vaccination::Column::Vaccine)
+            .column(vaccination::Column::GivenOn)
+            .column(vaccination::Column::LotNumber)
+            .column(vaccination::Column::NextDueOn)
+            .column_as(vet::Column::FirstName, "vet_first_name")
+            .column_as(vet::Column::LastName, "vet_last_name")
+            .order_by_desc(vaccination::Column::GivenOn)
+            .order_by_desc(vaccination::Column::Id)
+            .into_model::<VaccinationRow>()
+            .all(conn)
+            .await?;
+
+        let latest = latest_vaccination_ids(
+            rows.iter()
+                .map(|row| (row.id, row.pet_id, row.vaccine.as_str(), row.given_on)),
+        );
+        let today = Local::now().date_naive();
+
+        let vaccinations = rows
+            .into_iter()
+            .map(|row| {
+                let due_status = row
+                    .next_due_on
+                    .filter(|_| latest.contains(&row.id))
+                    .and_then(|next_due_on| due_status(next_due_on, today, due_soon_days));
+
+                Vaccination {
+                    id: row.id,
+                    pet_id: row.pet_id,
+                    vaccine: row.vaccine,
+                    given_on: row.given_on,
+                    lot_number: row.lot_number,
+                    next_due_on: row.next_due_on,
+                    vet_name: row
+                        .vet_first_name
+                        .zip(row.vet_last_name)
+                        .map(|(first_name, last_name)| format!("{first_name} {last_name}")),
+                    due_status,
+                }
+            })
+            .collect();
+
+        Ok(vaccinations)
+    }
+
+    /// 다음 접종일이 오늘부터 `days`일 안이거나 이미 지난 접종을 다음 접종일순으로 반환함.
+    /// 이후에 같은 백신을 다시 접종한 기록은 제외함
+    #[instrument(skip(conn))]
+    pub async fn fetch_due_vaccinations(
+        conn: &DbConn,
+        days: u32,
+    ) -> Result<Vec<DueVaccination>, AppError> {
+        let today = Local::now().date_naive();
+        let until = today + Days::new(u64::from(days));
+
+        let rows = vaccination::Entity::find()
+            .join(JoinType::InnerJoin, vaccination::Relation::Pets.def())
+            .join(JoinType::InnerJoin, pet::Relation::Owners.def())
+            .filter(vaccination::Column::NextDueOn.lte(until))
+            .select_only()
+            .column(vaccination::Column::Id)
+            .column(vaccination::Column::PetId)
+            .column(vaccination::Column::Vaccine)
+            .column(vaccination::Column::NextDueOn)
+            .column_as(pet::Column::Name, "pet_name")
+            .column_as(owners::Column::Id, "owner_id")
+            .column_as(owners::Column::FirstName, "owner_first_name")
+            .column_as(owners::Column::LastName, "owner_last_name")
+            .order_by_asc(vaccination::Column::NextDueOn)
+            .order_by_asc(pet::Column::Name)
+            .into_model::<DueVaccinationRow>()
+            .all(conn)
+            .await?;
+
+        // 다시 접종했는지 확인하려면 보고서 대상 pet의 모든 접종 기록이 필요함
+        let pet_ids: Vec<i32> = rows.iter().map(|row| row.pet_id).collect();
+        let history = vaccination::Entity::find()
+            .filter(vaccination::Column::PetId.is_in(pet_ids))
+            .select_only()
+            .column(vaccination::Column::Id)
+            .column(vaccination::Column::PetId)
+            .column(vaccination::Column::Vaccine)
+            .column(vaccination::Column::GivenOn)
+            .into_tuple::<(i32, i32, String, Date)>()
+            .all(conn)
+            .await?;
+        let latest =
+            latest_vaccination_ids(history.iter().map(|(id, pet_id, vaccine, given_on)| {
+                (*id, *pet_id, vaccine.as_str(), *given_on)
+            }));
+
+        let due_vaccinations = rows
+            .into_iter()
+            .filter(|row| latest.contains(&row.id))
+            .map(|row| DueVaccination {
+                due_status: due_status(row.next_due_on, today, days),
+                pet_id: row.pet_id,
+                pet_name: row.pet_name,
+                owner_id: row.owner_id,
+                owner_first_name: row.owner_first_name,
+                owner_last_name: row.owner_last_name,
+                vaccine: row.vaccine,
+                next_due_on: row.next_due_on,
+            })
+            .collect();
+
+        Ok(due_vaccinations)
+    }
+}
+
+/// pet과 백신 이름(대소문자 무시)별로 가장 최근에 접종한 기록의 id
+fn latest_vaccination_ids<'a>(
+    records: impl Iterator<Item = (i32, i32, &'a str, Date)>,
+) -> Vec<i32> {
+    let mut latest: HashMap<(i32, String), (Date, i32)> = HashMap::new();
+    for (id, pet_id, vaccine, given_on) in records {
+        latest
+            .entry((pet_id, vaccine.to_lowercase()))
+            .and_modify(|current| *current = (*current).max((given_on, id)))
+            .or_insert((given_on, id));
+    }
+
+    latest.into_values().map(|(_, id)| id).collect()
+}
+
+fn due_status(next_due_on: Date, today: Date, due_soon_days: u32) -> Option<DueStatus> {
+    if next_due_on < today {
+        Some(DueStatus::Overdue)
+    } else if next_due_on <= today + Days::new(u64::from(due_soon_days)) {
+        Some(DueStatus::DueSoon)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_due_status() {
+        let today = date("2026-10-17");
+
+        assert_eq!(
+            due_status(date("2026-10-16"), today, 30),
+            Some(DueStatus::Overdue)
+        );
+        assert_eq!(
+            due_status(date("2026-10-17"), today, 30),
+            Some(DueStatus::DueSoon)
+        );
+        assert_eq!(
+            due_status(date("2026-11-16"), today, 30),
+            Some(DueStatus::DueSoon)
+        );
+        assert_eq!(due_status(date("2026-11-17"), today, 30), None);
+    }
+
+    #[test]
+    fn test_latest_vaccination_ids_ignores_superseded_records() {
+        let records = [
+            (1, 7, "Rabies", date("2025-10-01")),
+            (2, 7, "rabies", date("2026-10-01")),
+            (3, 7, "Distemper", date("2025-10-01")),
+            (4, 8, "Rabies", date("2025-10-01")),
+        ];
+
+        let mut latest = latest_vaccination_ids(records.into_iter());
+        latest.sort();
+
+        assert_eq!(latest, vec![2, 3, 4]);
+    }
+}
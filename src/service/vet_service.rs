@@ -1,42 +1,73 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use chrono::Local;
 use sea_orm::{
-    ColumnTrait, DbConn, EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter,
-    QueryOrder, QuerySelect, RelationTrait,
+    prelude::{Date, DateTime},
+    ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DatabaseTransaction, DbConn,
+    EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+    RelationTrait, TransactionTrait,
 };
 use serde::Serialize;
+use tracing::{field, instrument, Span};
 
 use crate::{
-    domain::veterinarian::{specialty, vet, vet_specialty},
+    domain::{
+        owner::{
+            owners, pet,
+            visit::{self, VisitStatus},
+        },
+        veterinarian::{specialty, vet, vet_specialty},
+    },
     model::app_error::AppError,
+    service::visit_service::VisitService,
 };
 
 pub struct VetService;
 
 #[derive(Serialize, FromQueryResult)]
 struct VetWithSpecialtiesQueryResult {
-    vet_id: u32,
+    vet_id: i32,
     first_name: Option<String>,
     last_name: Option<String>,
-    specialty_id: Option<u32>,
+    active: bool,
+    specialty_id: Option<i32>,
     specialty_name: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct VetWithSpecialties {
-    pub vet_id: u32,
+    pub vet_id: i32,
     pub first_name: Option<String>,
     pub last_name: Option<String>,
+    pub active: bool,
     pub specialties: Vec<Specialty>,
 }
 
 #[derive(Serialize)]
 pub struct Specialty {
-    pub specialty_id: u32,
+    pub specialty_id: i32,
     pub specialty_name: Option<String>,
 }
 
+#[derive(Serialize, FromQueryResult)]
+pub struct VisitOfVet {
+    visit_id: i32,
+    visit_date: Option<Date>,
+    starts_at: Option<DateTime>,
+    duration_minutes: Option<i32>,
+    room: Option<String>,
+    description: Option<String>,
+    status: VisitStatus,
+    pet_id: i32,
+    pet_name: Option<String>,
+    owner_id: i32,
+    owner_first_name: Option<String>,
+    owner_last_name: Option<String>,
+}
+
 impl VetService {
+    /// 은퇴하지 않은 vet만 반환함
+    #[instrument(skip(conn))]
     pub async fn fetch_all_vets_with_specialties(
         conn: &DbConn,
     ) -> Result<Vec<VetWithSpecialties>, AppError> {
@@ -46,10 +77,12 @@ impl VetService {
                 JoinType::LeftJoin,
                 vet_specialty::Relation::Specialties.def(),
             )
+            .filter(vet::Column::Active.eq(true))
             .select_only()
             .column_as(vet::Column::Id, "vet_id")
             .column(vet::Column::FirstName)
             .column(vet::Column::LastName)
+            .column(vet::Column::Active)
             .column_as(specialty::Column::Id, "specialty_id")
             .column_as(specialty::Column::Name, "specialty_name")
             .order_by_asc(vet::Column::Id)
@@ -64,11 +97,12 @@ impl VetService {
     fn group_vets_by_id(
         vets_with_specialties: Vec<VetWithSpecialtiesQueryResult>,
     ) -> Vec<VetWithSpecialties> {
-        let mut vet_map: BTreeMap<u32, VetWithSpecialties> = BTreeMap::new();
+        let mut vet_map: BTreeMap<i32, VetWithSpecialties> = BTreeMap::new();
         for VetWithSpecialtiesQueryResult {
             vet_id,
             first_name,
             last_name,
+            active,
             specialty_id,
             specialty_name,
         } in vets_with_specialties
@@ -77,6 +111,7 @@ impl VetService {
                 vet_id,
                 first_name,
                 last_name,
+                active,
                 specialties: Vec::new(),
             });
 
@@ -91,15 +126,22 @@ impl VetService {
         vet_map.into_values().collect()
     }
 
+    #[instrument(skip(conn))]
     pub async fn fetch_vets_with_specialties_paginated(
         conn: &DbConn,
         page: u64,
         size: u64,
+        include_retired: bool,
     ) -> Result<Vec<VetWithSpecialties>, AppError> {
-        let vet_ids: Vec<u32> = vet::Entity::find()
+        let mut vet_query = vet::Entity::find();
+        if !include_retired {
+            vet_query = vet_query.filter(vet::Column::Active.eq(true));
+        }
+
+        let vet_ids: Vec<i32> = vet_query
             .order_by_asc(vet::Column::Id)
             .paginate(conn, size)
-            .fetch_page(page - 1)
+            .fetch_page(page.saturating_sub(1))
             .await?
             .into_iter()
             .map(|vet| vet.id)
@@ -116,6 +158,7 @@ impl VetService {
             .column_as(vet::Column::Id, "vet_id")
             .column(vet::Column::FirstName)
             .column(vet::Column::LastName)
+            .column(vet::Column::Active)
             .column_as(specialty::Column::Id, "specialty_id")
             .column_as(specialty::Column::Name, "specialty_name")
             .into_model::<VetWithSpecialtiesQueryResult>()
@@ -125,9 +168,341 @@ impl VetService {
         Ok(Self::group_vets_by_id(vets_with_specialties_paginated))
     }
 
-    pub async fn fetch_all_vets_count(conn: &DbConn) -> Result<u64, AppError> {
-        let vet_total_count = vet::Entity::find().count(conn).await?;
+    #[instrument(skip(conn))]
+    pub async fn fetch_all_vets_count(
+        conn: &DbConn,
+        include_retired: bool,
+    ) -> Result<u64, AppError> {
+        let mut vet_query = vet::Entity::find();
+        if !include_retired {
+            vet_query = vet_query.filter(vet::Column::Active.eq(true));
+        }
+
+        let vet_total_count = vet_query.count(conn).await?;
 
         Ok(vet_total_count)
     }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_vet_by_id<C: ConnectionTrait>(
+        conn: &C,
+        vet_id: i32,
+    ) -> Result<vet::Model, AppError> {
+        vet::Entity::find_by_id(vet_id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "vet".to_string(),
+                id: vet_id,
+            })
+    }
+
+    /// 새 visit을 배정할 수 있는, 은퇴하지 않은 vet만 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_active_vets(conn: &DbConn) -> Result<Vec<vet::Model>, AppError> {
+        let vets = vet::Entity::find()
+            .filter(vet::Column::Active.eq(true))
+            .order_by_asc(vet::Column::LastName)
+            .order_by_asc(vet::Column::FirstName)
+            .all(conn)
+            .await?;
+
+        Ok(vets)
+    }
+
+    /// 해당 날짜에 예약된 visit이 `max_visits_per_day`보다 적은 vet만 반환함.
+    /// 수정 중인 visit은 자기 vet의 예약 수에서 제외하기 위해 `exclude_visit_id`로 넘김
+    #[instrument(skip(conn))]
+    pub async fn fetch_available_vets(
+        conn: &DbConn,
+        date: Date,
+        max_visits_per_day: u64,
+        exclude_visit_id: Option<i32>,
+    ) -> Result<Vec<vet::Model>, AppError> {
+        let mut booked_query = visit::Entity::find()
+            .select_only()
+            .column(visit::Column::VetId)
+            .column_as(visit::Column::Id.count(), "visit_count")
+            .filter(visit::Column::VisitDate.eq(date))
+            .filter(visit::Column::Status.eq(VisitStatus::Scheduled))
+            .filter(visit::Column::VetId.is_not_null())
+            .group_by(visit::Column::VetId);
+        if let Some(visit_id) = exclude_visit_id {
+            booked_query = booked_query.filter(visit::Column::Id.ne(visit_id));
+        }
+
+        let booked: HashMap<i32, i64> = booked_query
+            .into_tuple::<(i32, i64)>()
+            .all(conn)
+            .await?
+            .into_iter()
+            .collect();
+
+        let available_vets = Self::fetch_active_vets(conn)
+            .await?
+            .into_iter()
+            .filter(|v| {
+                booked
+                    .get(&v.id)
+                    .is_none_or(|&count| (count as u64) < max_visits_per_day)
+            })
+            .collect();
+
+        Ok(available_vets)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_visits_by_vet_paginated(
+        conn: &DbConn,
+        vet_id: i32,
+        page: u64,
+        size: u64,
+    ) -> Result<Vec<VisitOfVet>, AppError> {
+        let visits = visit::Entity::find()
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .join(JoinType::InnerJoin, pet::Relation::Owners.def())
+            .filter(visit::Column::VetId.eq(vet_id))
+            .select_only()
+            .column_as(visit::Column::Id, "visit_id")
+            .column(visit::Column::VisitDate)
+            .column(visit::Column::StartsAt)
+            .column(visit::Column::DurationMinutes)
+            .column(visit::Column::Room)
+            .column(visit::Column::Description)
+            .column(visit::Column::Status)
+            .column_as(pet::Column::Id, "pet_id")
+            .column_as(pet::Column::Name, "pet_name")
+            .column_as(owners::Column::Id, "owner_id")
+            .column_as(owners::Column::FirstName, "owner_first_name")
+            .column_as(owners::Column::LastName, "owner_last_name")
+            .order_by_desc(visit::Column::VisitDate)
+            .order_by_desc(visit::Column::StartsAt)
+            .order_by_desc(visit::Column::Id)
+            .into_model::<VisitOfVet>()
+            .paginate(conn, size)
+            .fetch_page(page.saturating_sub(1))
+            .await?;
+
+        Ok(visits)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_visit_count_by_vet(conn: &DbConn, vet_id: i32) -> Result<u64, AppError> {
+        let visit_count = visit::Entity::find()
+            .filter(visit::Column::VetId.eq(vet_id))
+            .count(conn)
+            .await?;
+
+        Ok(visit_count)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn fetch_specialty_ids_by_vet(
+        conn: &DbConn,
+        vet_id: i32,
+    ) -> Result<Vec<i32>, AppError> {
+        let specialty_ids = vet_specialty::Entity::find()
+            .filter(vet_specialty::Column::VetId.eq(vet_id))
+            .select_only()
+            .column(vet_specialty::Column::SpecialtyId)
+            .into_tuple::<i32>()
+            .all(conn)
+            .await?;
+
+        Ok(specialty_ids)
+    }
+
+    #[instrument(skip_all, fields(vet_id = field::Empty))]
+    pub async fn save_vet(
+        conn: &DbConn,
+        first_name: Option<String>,
+        last_name: Option<String>,
+        specialty_ids: &[i32],
+    ) -> Result<vet::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let vet_active_model = vet::ActiveModel {
+            first_name: ActiveValue::Set(first_name),
+            last_name: ActiveValue::Set(last_name),
+            active: ActiveValue::Set(true),
+            ..Default::default()
+        };
+        let new_vet = vet_active_model.insert(&txn).await?;
+        Span::current().record("vet_id", new_vet.id);
+
+        Self::replace_specialties(&txn, new_vet.id, specialty_ids).await?;
+
+        txn.commit().await?;
+
+        Ok(new_vet)
+    }
+
+    /// vet_specialties는 form에서 고른 specialty로 통째로 교체함
+    #[instrument(skip_all, fields(vet_id = vet_id))]
+    pub async fn update_vet(
+        conn: &DbConn,
+        vet_id: i32,
+        first_name: Option<String>,
+        last_name: Option<String>,
+        specialty_ids: &[i32],
+    ) -> Result<vet::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        let mut vet_active_model: vet::ActiveModel = vet::Entity::find_by_id(vet_id)
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "vet".to_string(),
+                id: vet_id,
+            })?
+            .into();
+        vet_active_model.first_name = ActiveValue::Set(first_name);
+        vet_active_model.last_name = ActiveValue::Set(last_name);
+        let updated_vet = vet_active_model.update(&txn).await?;
+
+        Self::replace_specialties(&txn, vet_id, specialty_ids).await?;
+
+        txn.commit().await?;
+
+        Ok(updated_vet)
+    }
+
+    async fn replace_specialties(
+        txn: &DatabaseTransaction,
+        vet_id: i32,
+        specialty_ids: &[i32],
+    ) -> Result<(), AppError> {
+        vet_specialty::Entity::delete_many()
+            .filter(vet_specialty::Column::VetId.eq(vet_id))
+            .exec(txn)
+            .await?;
+
+        // 중복된 id는 primary key 충돌이 나므로 제거함
+        let specialty_ids: BTreeSet<i32> = specialty_ids.iter().copied().collect();
+        if !specialty_ids.is_empty() {
+            let vet_specialties =
+                specialty_ids
+                    .into_iter()
+                    .map(|specialty_id| vet_specialty::ActiveModel {
+                        vet_id: ActiveValue::Set(vet_id),
+                        specialty_id: ActiveValue::Set(specialty_id),
+                    });
+            vet_specialty::Entity::insert_many(vet_specialties)
+                .exec(txn)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 오늘 이후로 예약된 visit이 남아 있는 vet은 은퇴시키지 않음. 지난 visit은 기록으로 남음
+    #[instrument(skip(conn))]
+    pub async fn retire_vet(conn: &DbConn, vet_id: i32) -> Result<vet::Model, AppError> {
+        // 확인과 은퇴 사이에 새 예약이 들어오지 않도록 예약 저장과 같은 lock 안에서 처리함
+        let txn = conn.begin().await?;
+        VisitService::lock_schedule(&txn).await?;
+
+        let upcoming_visit_count = visit::Entity::find()
+            .filter(visit::Column::VetId.eq(vet_id))
+            .filter(visit::Column::Status.eq(VisitStatus::Scheduled))
+            .filter(visit::Column::VisitDate.gte(Local::now().date_naive()))
+            .count(&txn)
+            .await?;
+        if upcoming_visit_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "vet".to_string(),
+                id: vet_id,
+                dependents: format!("{upcoming_visit_count} upcoming visit(s)"),
+            });
+        }
+
+        let retired_vet = Self::set_active(&txn, vet_id, false).await?;
+        txn.commit().await?;
+
+        Ok(retired_vet)
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn reinstate_vet(conn: &DbConn, vet_id: i32) -> Result<vet::Model, AppError> {
+        Self::set_active(conn, vet_id, true).await
+    }
+
+    async fn set_active<C: ConnectionTrait>(
+        conn: &C,
+        vet_id: i32,
+        active: bool,
+    ) -> Result<vet::Model, AppError> {
+        let mut vet_active_model: vet::ActiveModel =
+            Self::fetch_vet_by_id(conn, vet_id).await?.into();
+        vet_active_model.active = ActiveValue::Set(active);
+
+        let updated_vet = vet_active_model.update(conn).await?;
+
+        Ok(updated_vet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Days;
+
+    use super::*;
+    use crate::{
+        config::db::connect_test_db,
+        service::visit_service::{Appointment, VisitService},
+    };
+
+    #[actix_web::test]
+    async fn test_save_vet_ignores_duplicate_specialties() {
+        let conn = connect_test_db().await;
+
+        let vet = VetService::save_vet(
+            &conn,
+            Some("James".to_string()),
+            Some("Wilson".to_string()),
+            &[2, 1, 2],
+        )
+        .await
+        .unwrap();
+
+        let mut specialty_ids = VetService::fetch_specialty_ids_by_vet(&conn, vet.id)
+            .await
+            .unwrap();
+        specialty_ids.sort_unstable();
+        assert_eq!(specialty_ids, vec![1, 2]);
+    }
+
+    #[actix_web::test]
+    async fn test_retire_vet_blocked_by_upcoming_visits() {
+        let conn = connect_test_db().await;
+
+        let starts_at = (Local::now().date_naive() + Days::new(7))
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let visit = VisitService::save_visit(
+            &conn,
+            Some(7),
+            None,
+            Appointment {
+                starts_at,
+                duration_minutes: 30,
+                vet_id: 1,
+                room: "Exam 1".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let result = VetService::retire_vet(&conn, 1).await;
+        assert!(matches!(result, Err(AppError::ResourceInUse { .. })));
+
+        visit::Entity::delete_by_id(visit.id)
+            .exec(&conn)
+            .await
+            .unwrap();
+        let retired_vet = VetService::retire_vet(&conn, 1).await.unwrap();
+        assert!(!retired_vet.active);
+        let active_vets = VetService::fetch_active_vets(&conn).await.unwrap();
+        assert!(active_vets.iter().all(|vet| vet.id != 1));
+    }
 }
@@ -1,25 +1,771 @@
-use sea_orm::{prelude::Date, ActiveModelTrait, ActiveValue, DbConn};
+use chrono::{Days, Local, TimeDelta};
+use sea_orm::{
+    prelude::DateTime, sea_query::Condition, ActiveModelTrait, ActiveValue, ColumnTrait,
+    ConnectionTrait, DatabaseTransaction, DbConn, EntityTrait, FromQueryResult, JoinType,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
+};
+use serde::Serialize;
+use tracing::{field, instrument, Span};
 
-use crate::{domain::owner::visit, model::app_error::AppError};
+use crate::{
+    config::{app::SchedulingConfig, metrics::METRICS},
+    domain::{
+        owner::{
+            billable_item, pet,
+            prescription::{self, PrescriptionKind},
+            visit::{self, VisitStatus},
+        },
+        veterinarian::vet,
+    },
+    model::app_error::AppError,
+    service::{attachment_service::AttachmentService, vet_service::VetService},
+    storage::Storage,
+};
+
+// 요청한 시각이 이미 예약된 경우 다음 빈 시간을 찾는 범위와 제안 개수
+const SUGGESTION_DAYS: u64 = 14;
+const SUGGESTION_COUNT: usize = 3;
 
 pub struct VisitService;
 
+/// 예약 시각과 길이, 담당 vet, 진료실
+#[derive(Clone, Debug)]
+pub struct Appointment {
+    pub starts_at: DateTime,
+    pub duration_minutes: i32,
+    pub vet_id: i32,
+    pub room: String,
+}
+
+/// visit form의 한 줄로 입력한 처치 또는 처방
+#[derive(Clone, Debug)]
+pub struct NewPrescription {
+    pub kind: PrescriptionKind,
+    pub drug: String,
+    pub dose: String,
+    pub frequency: String,
+    pub duration_days: i32,
+    pub vet_id: i32,
+}
+
+#[derive(FromQueryResult)]
+struct PrescriptionRow {
+    visit_id: i32,
+    kind: PrescriptionKind,
+    drug: String,
+    dose: String,
+    frequency: String,
+    duration_days: i32,
+    vet_id: i32,
+    vet_first_name: Option<String>,
+    vet_last_name: Option<String>,
+}
+
+/// visit 기록과 처방전에 표시할 처치/처방
+#[derive(Serialize)]
+pub struct Prescription {
+    pub visit_id: i32,
+    kind: PrescriptionKind,
+    drug: String,
+    dose: String,
+    frequency: String,
+    duration_days: i32,
+    vet_id: i32,
+    vet_name: String,
+}
+
+impl Appointment {
+    pub fn ends_at(&self) -> DateTime {
+        self.starts_at + TimeDelta::minutes(i64::from(self.duration_minutes))
+    }
+
+    /// 진료 시간 안에서 slot 경계에 시작하는지 확인함
+    pub fn fits_schedule(&self, scheduling: &SchedulingConfig) -> bool {
+        let start = self.starts_at.time();
+        let from_opening = (start - scheduling.opening_time).num_minutes();
+
+        start >= scheduling.opening_time
+            && self.ends_at() <= self.starts_at.date().and_time(scheduling.closing_time)
+            && from_opening % i64::from(scheduling.slot_minutes) == 0
+    }
+}
+
 impl VisitService {
-    pub async fn save_visit(
-        conn: &DbConn,
-        pet_id: Option<u32>,
-        visit_date: Option<Date>,
+    #[instrument(skip_all, fields(pet_id = pet_id, visit_id = field::Empty))]
+    pub async fn save_visit<C: ConnectionTrait>(
+        conn: &C,
+        pet_id: Option<i32>,
         description: Option<String>,
+        appointment: Appointment,
     ) -> Result<visit::Model, AppError> {
         let visit_active_model = visit::ActiveModel {
             pet_id: ActiveValue::Set(pet_id),
-            visit_date: ActiveValue::Set(visit_date),
+            visit_date: ActiveValue::Set(Some(appointment.starts_at.date())),
             description: ActiveValue::Set(description),
+            vet_id: ActiveValue::Set(Some(appointment.vet_id)),
+            starts_at: ActiveValue::Set(Some(appointment.starts_at)),
+            duration_minutes: ActiveValue::Set(Some(appointment.duration_minutes)),
+            room: ActiveValue::Set(Some(appointment.room)),
+            status: ActiveValue::Set(VisitStatus::Scheduled),
             ..Default::default()
         };
 
         let new_owner = visit_active_model.insert(conn).await?;
+        Span::current().record("visit_id", new_owner.id);
+        METRICS.visits_booked.inc();
 
         Ok(new_owner)
     }
+
+    /// 같은 vet이나 같은 진료실과 겹치는 예약이 없을 때만 처치/처방과 함께 저장함
+    #[instrument(skip_all, fields(pet_id = pet_id, vet_id = appointment.vet_id))]
+    pub async fn schedule_visit(
+        conn: &DbConn,
+        scheduling: &SchedulingConfig,
+        pet_id: i32,
+        description: Option<String>,
+        appointment: Appointment,
+        prescriptions: &[NewPrescription],
+    ) -> Result<visit::Model, AppError> {
+        let txn = conn.begin().await?;
+
+        Self::lock_schedule(&txn).await?;
+        Self::ensure_vet_is_active(&txn, appointment.vet_id).await?;
+        Self::ensure_slot_is_free(&txn, scheduling, &appointment, None).await?;
+        let new_visit = Self::save_visit(&txn, Some(pet_id), description, appointment).await?;
+        Self::replace_prescriptions(&txn, new_visit.id, prescriptions).await?;
+
+        txn.commit().await?;
+
+        Ok(new_visit)
+    }
+
+    /// 동시에 들어온 예약이 둘 다 겹치지 않는다고 보고 저장하지 않도록, 확인부터 commit까지
+    /// vet row를 id 순서로 잠가 예약 저장을 직렬화함. vet이 달라도 진료실이 겹칠 수 있으므로 모두 잠금.
+    /// SQLite는 row lock이 없지만 쓰기 transaction을 하나씩만 실행함.
+    /// vet 은퇴도 같은 lock을 잡아 예약 확인과 은퇴가 엇갈리지 않게 함
+    pub(crate) async fn lock_schedule(txn: &DatabaseTransaction) -> Result<(), AppError> {
+        vet::Entity::find()
+            .select_only()
+            .column(vet::Column::Id)
+            .order_by_asc(vet::Column::Id)
+            .lock_exclusive()
+            .into_tuple::<i32>()
+            .all(txn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 폼 검증 이후에 은퇴한 vet에게는 예약을 저장하지 않음.
+    /// lock_schedule로 잠근 transaction 안에서 호출해야 함
+    async fn ensure_vet_is_active(txn: &DatabaseTransaction, vet_id: i32) -> Result<(), AppError> {
+        let vet = VetService::fetch_vet_by_id(txn, vet_id).await?;
+        if !vet.active {
+            return Err(AppError::InvalidResourceState {
+                resource: "vet".to_string(),
+                id: vet_id,
+                state: "retired".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// 겹치는 예약이 있으면 요청한 시각 이후의 빈 시간을 담아 conflict로 반환함.
+    /// lock_schedule로 잠근 transaction 안에서 호출해야 함
+    async fn ensure_slot_is_free(
+        txn: &DatabaseTransaction,
+        scheduling: &SchedulingConfig,
+        appointment: &Appointment,
+        exclude_visit_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        let booked =
+            Self::fetch_booked_slots(txn, appointment, SUGGESTION_DAYS, exclude_visit_id).await?;
+
+        let is_taken = booked.iter().any(|&(starts_at, duration_minutes)| {
+            overlaps(
+                appointment.starts_at,
+                appointment.duration_minutes,
+                starts_at,
+                duration_minutes,
+            )
+        });
+        if !is_taken {
+            return Ok(());
+        }
+
+        Err(AppError::AppointmentConflict {
+            starts_at: appointment.starts_at,
+            next_free_slots: next_free_slots(
+                &booked,
+                appointment,
+                scheduling,
+                Local::now().naive_local(),
+            ),
+        })
+    }
+
+    /// 요청한 날부터 `days`일 동안 같은 vet 또는 같은 진료실에 잡힌 예약의 시작 시각과 길이
+    async fn fetch_booked_slots(
+        txn: &DatabaseTransaction,
+        appointment: &Appointment,
+        days: u64,
+        exclude_visit_id: Option<i32>,
+    ) -> Result<Vec<(DateTime, i32)>, AppError> {
+        let from = appointment.starts_at.date().and_time(Default::default());
+        let until = from + Days::new(days);
+
+        let mut query = visit::Entity::find()
+            .select_only()
+            .column(visit::Column::StartsAt)
+            .column(visit::Column::DurationMinutes)
+            .filter(visit::Column::Status.eq(VisitStatus::Scheduled))
+            .filter(visit::Column::StartsAt.gte(from))
+            .filter(visit::Column::StartsAt.lt(until))
+            .filter(visit::Column::DurationMinutes.is_not_null())
+            .filter(
+                Condition::any()
+                    .add(visit::Column::VetId.eq(appointment.vet_id))
+                    .add(visit::Column::Room.eq(appointment.room.as_str())),
+            );
+        if let Some(visit_id) = exclude_visit_id {
+            query = query.filter(visit::Column::Id.ne(visit_id));
+        }
+
+        let booked = query.into_tuple::<(DateTime, i32)>().all(txn).await?;
+
+        Ok(booked)
+    }
+
+    /// visit이 path의 pet에, pet이 path의 owner에 속할 때만 반환하고 아니면 not found로 처리함
+    #[instrument(skip(conn))]
+    pub async fn fetch_visit_of_pet(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+    ) -> Result<visit::Model, AppError> {
+        visit::Entity::find_by_id(visit_id)
+            .inner_join(pet::Entity)
+            .filter(visit::Column::PetId.eq(pet_id))
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(conn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "visit".to_string(),
+                id: visit_id,
+            })
+    }
+
+    /// 처치/처방은 form에서 입력한 줄로 통째로 교체함
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip_all, fields(pet_id = pet_id, visit_id = visit_id))]
+    pub async fn update_visit(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+        scheduling: &SchedulingConfig,
+        description: Option<String>,
+        appointment: Appointment,
+        prescriptions: &[NewPrescription],
+    ) -> Result<visit::Model, AppError> {
+        let visit = Self::fetch_visit_of_pet(conn, owner_id, pet_id, visit_id).await?;
+        if visit.status == VisitStatus::Cancelled {
+            return Err(AppError::InvalidResourceState {
+                resource: "visit".to_string(),
+                id: visit_id,
+                state: "cancelled".to_string(),
+            });
+        }
+
+        // 예약을 바꾸지 않고 설명이나 처치/처방만 고치는 경우에는 겹치는 예약을 다시 확인하지 않음
+        let is_rescheduled = visit.starts_at != Some(appointment.starts_at)
+            || visit.duration_minutes != Some(appointment.duration_minutes)
+            || visit.vet_id != Some(appointment.vet_id)
+            || visit.room.as_deref() != Some(appointment.room.as_str());
+
+        let txn = conn.begin().await?;
+
+        if is_rescheduled {
+            Self::lock_schedule(&txn).await?;
+            Self::ensure_vet_is_active(&txn, appointment.vet_id).await?;
+            Self::ensure_slot_is_free(&txn, scheduling, &appointment, Some(visit_id)).await?;
+        }
+
+        let visit_active_model = visit::ActiveModel {
+            id: ActiveValue::Unchanged(visit_id),
+            visit_date: ActiveValue::Set(Some(appointment.starts_at.date())),
+            description: ActiveValue::Set(description),
+            vet_id: ActiveValue::Set(Some(appointment.vet_id)),
+            starts_at: ActiveValue::Set(Some(appointment.starts_at)),
+            duration_minutes: ActiveValue::Set(Some(appointment.duration_minutes)),
+            room: ActiveValue::Set(Some(appointment.room)),
+            ..Default::default()
+        };
+
+        let updated_visit = visit_active_model.update(&txn).await?;
+        Self::replace_prescriptions(&txn, visit_id, prescriptions).await?;
+
+        txn.commit().await?;
+
+        Ok(updated_visit)
+    }
+
+    async fn replace_prescriptions(
+        txn: &DatabaseTransaction,
+        visit_id: i32,
+        prescriptions: &[NewPrescription],
+    ) -> Result<(), AppError> {
+        prescription::Entity::delete_many()
+            .filter(prescription::Column::VisitId.eq(visit_id))
+            .exec(txn)
+            .await?;
+
+        if !prescriptions.is_empty() {
+            let rows = prescriptions
+                .iter()
+                .map(|new_prescription| prescription::ActiveModel {
+                    visit_id: ActiveValue::Set(visit_id),
+                    kind: ActiveValue::Set(new_prescription.kind),
+                    drug: ActiveValue::Set(new_prescription.drug.clone()),
+                    dose: ActiveValue::Set(new_prescription.dose.clone()),
+                    frequency: ActiveValue::Set(new_prescription.frequency.clone()),
+                    duration_days: ActiveValue::Set(new_prescription.duration_days),
+                    vet_id: ActiveValue::Set(new_prescription.vet_id),
+                    ..Default::default()
+                });
+            prescription::Entity::insert_many(rows).exec(txn).await?;
+        }
+
+        Ok(())
+    }
+
+    /// owner의 모든 visit의 처치/처방을 입력한 순서대로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_prescriptions_by_owner(
+        conn: &DbConn,
+        owner_id: i32,
+    ) -> Result<Vec<Prescription>, AppError> {
+        let rows = prescription::Entity::find()
+            .join(JoinType::InnerJoin, prescription::Relation::Visits.def())
+            .join(JoinType::InnerJoin, visit::Relation::Pets.def())
+            .join(JoinType::InnerJoin, prescription::Relation::Vets.def())
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .select_only()
+            .column(prescription::Column::VisitId)
+            .column(prescription::Column::Kind)
+            .column(prescription::Column::Drug)
+            .column(prescription::Column::Dose)
+            .column(prescription::Column::Frequency)
+            .column(prescription::Column::DurationDays)
+            .column(prescription::Column::VetId)
+            .column_as(vet::Column::FirstName, "vet_first_name")
+            .column_as(vet::Column::LastName, "vet_last_name")
+            .order_by_asc(prescription::Column::Id)
+            .into_model::<PrescriptionRow>()
+            .all(conn)
+            .await?;
+
+        let prescriptions = rows
+            .into_iter()
+            .map(|row| Prescription {
+                visit_id: row.visit_id,
+                kind: row.kind,
+                drug: row.drug,
+                dose: row.dose,
+                frequency: row.frequency,
+                duration_days: row.duration_days,
+                vet_id: row.vet_id,
+                vet_name: format!(
+                    "{} {}",
+                    row.vet_first_name.unwrap_or_default(),
+                    row.vet_last_name.unwrap_or_default()
+                ),
+            })
+            .collect();
+
+        Ok(prescriptions)
+    }
+
+    /// visit 수정 form을 채우기 위해 입력한 순서대로 반환함
+    #[instrument(skip(conn))]
+    pub async fn fetch_prescriptions_by_visit(
+        conn: &DbConn,
+        visit_id: i32,
+    ) -> Result<Vec<prescription::Model>, AppError> {
+        let prescriptions = prescription::Entity::find()
+            .filter(prescription::Column::VisitId.eq(visit_id))
+            .order_by_asc(prescription::Column::Id)
+            .all(conn)
+            .await?;
+
+        Ok(prescriptions)
+    }
+
+    /// 기록은 남겨두고 status만 cancelled로 바꿈. 이미 취소된 visit은 그대로 반환함
+    #[instrument(skip_all, fields(pet_id = pet_id, visit_id = visit_id))]
+    pub async fn cancel_visit(
+        conn: &DbConn,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+    ) -> Result<visit::Model, AppError> {
+        let visit = Self::fetch_visit_of_pet(conn, owner_id, pet_id, visit_id).await?;
+        if visit.status == VisitStatus::Cancelled {
+            return Ok(visit);
+        }
+
+        let visit_active_model = visit::ActiveModel {
+            id: ActiveValue::Unchanged(visit_id),
+            status: ActiveValue::Set(VisitStatus::Cancelled),
+            ..Default::default()
+        };
+
+        let cancelled_visit = visit_active_model.update(conn).await?;
+
+        Ok(cancelled_visit)
+    }
+
+    /// owner의 pet에 속한 visit만 삭제함. 다른 owner나 pet의 visit id가 들어오면 not found로 처리함.
+    /// 처치/처방, 청구하지 않은 항목, 첨부 파일은 함께 삭제하고, 청구한 항목이 있으면 삭제하지 않음
+    #[instrument(skip(conn, storage))]
+    pub async fn delete_visit(
+        conn: &DbConn,
+        storage: &dyn Storage,
+        owner_id: i32,
+        pet_id: i32,
+        visit_id: i32,
+    ) -> Result<(), AppError> {
+        let txn = conn.begin().await?;
+
+        visit::Entity::find_by_id(visit_id)
+            .inner_join(pet::Entity)
+            .filter(visit::Column::PetId.eq(pet_id))
+            .filter(pet::Column::OwnerId.eq(owner_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| AppError::ResourceNotFound {
+                resource: "visit".to_string(),
+                id: visit_id,
+            })?;
+
+        let billed_count = billable_item::Entity::find()
+            .filter(billable_item::Column::VisitId.eq(visit_id))
+            .filter(billable_item::Column::InvoiceId.is_not_null())
+            .count(&txn)
+            .await?;
+        if billed_count > 0 {
+            return Err(AppError::ResourceInUse {
+                resource: "visit".to_string(),
+                id: visit_id,
+                dependents: format!("{billed_count} billed item(s)"),
+            });
+        }
+
+        billable_item::Entity::delete_many()
+            .filter(billable_item::Column::VisitId.eq(visit_id))
+            .exec(&txn)
+            .await?;
+        prescription::Entity::delete_many()
+            .filter(prescription::Column::VisitId.eq(visit_id))
+            .exec(&txn)
+            .await?;
+        let attachment_keys =
+            AttachmentService::delete_attachments_of_visits(&txn, &[visit_id]).await?;
+        visit::Entity::delete_by_id(visit_id).exec(&txn).await?;
+
+        txn.commit().await?;
+
+        AttachmentService::delete_files(storage, &attachment_keys).await;
+
+        Ok(())
+    }
+}
+
+fn overlaps(a_start: DateTime, a_minutes: i32, b_start: DateTime, b_minutes: i32) -> bool {
+    let a_end = a_start + TimeDelta::minutes(i64::from(a_minutes));
+    let b_end = b_start + TimeDelta::minutes(i64::from(b_minutes));
+
+    a_start < b_end && b_start < a_end
+}
+
+/// 요청한 시각 이후부터 진료 시간 안의 slot을 차례로 확인해 비어 있는 시작 시각을 찾음
+fn next_free_slots(
+    booked: &[(DateTime, i32)],
+    appointment: &Appointment,
+    scheduling: &SchedulingConfig,
+    not_before: DateTime,
+) -> Vec<DateTime> {
+    let slot = TimeDelta::minutes(i64::from(scheduling.slot_minutes));
+    let duration = TimeDelta::minutes(i64::from(appointment.duration_minutes));
+
+    let mut free_slots = Vec::new();
+    for day in appointment
+        .starts_at
+        .date()
+        .iter_days()
+        .take(SUGGESTION_DAYS as usize)
+    {
+        let closing = day.and_time(scheduling.closing_time);
+        let mut candidate = day.and_time(scheduling.opening_time);
+
+        while candidate + duration <= closing {
+            let is_free = booked.iter().all(|&(starts_at, minutes)| {
+                !overlaps(candidate, appointment.duration_minutes, starts_at, minutes)
+            });
+            if candidate > appointment.starts_at && candidate > not_before && is_free {
+                free_slots.push(candidate);
+                if free_slots.len() == SUGGESTION_COUNT {
+                    return free_slots;
+                }
+            }
+
+            candidate += slot;
+        }
+    }
+
+    free_slots
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::{config::db::connect_test_db, storage::memory::MemoryStorage};
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime {
+        NaiveDate::from_ymd_opt(2030, 5, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn appointment(starts_at: DateTime, duration_minutes: i32) -> Appointment {
+        Appointment {
+            starts_at,
+            duration_minutes,
+            vet_id: 1,
+            room: "Exam 1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_fits_schedule() {
+        let scheduling = SchedulingConfig::default();
+
+        assert!(appointment(at(6, 9, 0), 30).fits_schedule(&scheduling));
+        assert!(appointment(at(6, 16, 30), 30).fits_schedule(&scheduling));
+        assert!(!appointment(at(6, 8, 30), 30).fits_schedule(&scheduling));
+        assert!(!appointment(at(6, 16, 30), 60).fits_schedule(&scheduling));
+        assert!(!appointment(at(6, 9, 10), 30).fits_schedule(&scheduling));
+    }
+
+    #[test]
+    fn test_next_free_slots_skip_overlapping_bookings() {
+        let scheduling = SchedulingConfig::default();
+        let booked = [(at(6, 10, 0), 60), (at(6, 11, 30), 30), (at(6, 16, 0), 60)];
+
+        let slots = next_free_slots(
+            &booked,
+            &appointment(at(6, 10, 0), 30),
+            &scheduling,
+            at(1, 0, 0),
+        );
+        assert_eq!(slots, vec![at(6, 11, 0), at(6, 12, 0), at(6, 12, 30)]);
+
+        // 그날 남은 slot이 없으면 다음 날 진료 시작 시각부터 찾음
+        let slots = next_free_slots(
+            &booked,
+            &appointment(at(6, 15, 0), 60),
+            &scheduling,
+            at(1, 0, 0),
+        );
+        assert_eq!(slots, vec![at(7, 9, 0), at(7, 9, 30), at(7, 10, 0)]);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_visit_checks_owner() {
+        let conn = connect_test_db().await;
+        let storage = MemoryStorage::default();
+
+        // visit 4는 owner 6의 pet 7에 속함
+        let result = VisitService::delete_visit(&conn, &storage, 1, 7, 4).await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+        let result = VisitService::delete_visit(&conn, &storage, 6, 8, 4).await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+        assert!(visit::Entity::find_by_id(4)
+            .one(&conn)
+            .await
+            .unwrap()
+            .is_some());
+
+        VisitService::delete_visit(&conn, &storage, 6, 7, 4)
+            .await
+            .unwrap();
+        assert!(visit::Entity::find_by_id(4)
+            .one(&conn)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_cancel_visit_checks_owner_and_keeps_record() {
+        let conn = connect_test_db().await;
+        let scheduling = SchedulingConfig::default();
+
+        // visit 4는 owner 6의 pet 7에 속함
+        let result = VisitService::cancel_visit(&conn, 1, 7, 4).await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+        let result = VisitService::update_visit(
+            &conn,
+            1,
+            7,
+            4,
+            &scheduling,
+            None,
+            appointment(at(6, 9, 0), 30),
+            &[],
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::ResourceNotFound { .. })));
+
+        let cancelled_visit = VisitService::cancel_visit(&conn, 6, 7, 4).await.unwrap();
+        assert_eq!(cancelled_visit.status, VisitStatus::Cancelled);
+        let visit = VisitService::fetch_visit_of_pet(&conn, 6, 7, 4)
+            .await
+            .unwrap();
+        assert_eq!(visit.description.as_deref(), Some("spayed"));
+
+        // 취소한 visit은 다시 수정할 수 없음
+        let result = VisitService::update_visit(
+            &conn,
+            6,
+            7,
+            4,
+            &scheduling,
+            None,
+            appointment(at(6, 9, 0), 30),
+            &[],
+        )
+        .await;
+        assert!(matches!(result, Err(AppError::InvalidResourceState { .. })));
+    }
+
+    fn prescription(kind: PrescriptionKind, drug: &str) -> NewPrescription {
+        NewPrescription {
+            kind,
+            drug: drug.to_string(),
+            dose: "50mg".to_string(),
+            frequency: "twice daily".to_string(),
+            duration_days: 7,
+            vet_id: 2,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_update_visit_replaces_prescriptions() {
+        let conn = connect_test_db().await;
+        let scheduling = SchedulingConfig::default();
+
+        let visit = VisitService::schedule_visit(
+            &conn,
+            &scheduling,
+            7,
+            Some("dental cleaning".to_string()),
+            appointment(at(6, 10, 0), 30),
+            &[
+                prescription(PrescriptionKind::Treatment, "Scaling"),
+                prescription(PrescriptionKind::Prescription, "Amoxicillin"),
+            ],
+        )
+        .await
+        .unwrap();
+        let prescriptions = VisitService::fetch_prescriptions_by_visit(&conn, visit.id)
+            .await
+            .unwrap();
+        assert_eq!(prescriptions.len(), 2);
+
+        VisitService::update_visit(
+            &conn,
+            6,
+            7,
+            visit.id,
+            &scheduling,
+            Some("dental cleaning".to_string()),
+            appointment(at(6, 10, 0), 30),
+            &[prescription(PrescriptionKind::Prescription, "Meloxicam")],
+        )
+        .await
+        .unwrap();
+        let prescriptions = VisitService::fetch_prescriptions_by_visit(&conn, visit.id)
+            .await
+            .unwrap();
+        assert_eq!(prescriptions.len(), 1);
+        assert_eq!(prescriptions[0].drug, "Meloxicam");
+
+        // 처방전은 pet의 owner 기준으로만 보임
+        let prescriptions = VisitService::fetch_prescriptions_by_owner(&conn, 6)
+            .await
+            .unwrap();
+        assert!(prescriptions
+            .iter()
+            .any(|p| p.visit_id == visit.id && p.drug == "Meloxicam"));
+        let prescriptions = VisitService::fetch_prescriptions_by_owner(&conn, 1)
+            .await
+            .unwrap();
+        assert!(prescriptions.iter().all(|p| p.visit_id != visit.id));
+    }
+
+    #[actix_web::test]
+    async fn test_retired_vet_cannot_be_booked() {
+        let conn = connect_test_db().await;
+        let scheduling = SchedulingConfig::default();
+        let other_vet = Appointment {
+            vet_id: 2,
+            ..appointment(at(6, 10, 0), 30)
+        };
+        let visit = VisitService::schedule_visit(&conn, &scheduling, 7, None, other_vet, &[])
+            .await
+            .unwrap();
+
+        VetService::retire_vet(&conn, 1).await.unwrap();
+
+        let result = VisitService::schedule_visit(
+            &conn,
+            &scheduling,
+            7,
+            None,
+            appointment(at(7, 10, 0), 30),
+            &[],
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(AppError::InvalidResourceState { ref resource, id: 1, .. }) if resource == "vet"
+        ));
+
+        let result = VisitService::update_visit(
+            &conn,
+            6,
+            7,
+            visit.id,
+            &scheduling,
+            None,
+            appointment(at(6, 10, 0), 30),
+            &[],
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(AppError::InvalidResourceState { ref resource, id: 1, .. }) if resource == "vet"
+        ));
+        let stored = VisitService::fetch_visit_of_pet(&conn, 6, 7, visit.id)
+            .await
+            .unwrap();
+        assert_eq!(stored.vet_id, Some(2));
+    }
 }
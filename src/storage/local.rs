@@ -0,0 +1,62 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tokio::fs;
+
+use super::{validate_key, Storage};
+
+/// 설정한 root 디렉터리 아래에 key를 상대 경로로 삼아 파일을 저장함
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// root 디렉터리가 없으면 만듦
+    pub fn new(root: &Path) -> io::Result<Self> {
+        std::fs::create_dir_all(root).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to create storage root {}: {e}", root.display()),
+            )
+        })?;
+
+        Ok(LocalStorage {
+            root: root.to_path_buf(),
+        })
+    }
+
+    fn path_of(&self, key: &str) -> io::Result<PathBuf> {
+        validate_key(key)?;
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalStorage {
+    async fn put(&self, key: &str, content: &[u8]) -> io::Result<()> {
+        let path = self.path_of(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        // 쓰는 도중 읽히지 않도록 임시 파일에 쓴 뒤 이름을 바꿈
+        let temp_path = path.with_extension("partial");
+        fs::write(&temp_path, content).await?;
+        fs::rename(&temp_path, &path).await
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.path_of(key)?).await
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path_of(key)?).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{collections::HashMap, io, sync::RwLock};
+
+use async_trait::async_trait;
+
+use super::{validate_key, Storage};
+
+/// 파일을 process 메모리에만 두는 저장소. 재시작하면 모두 사라짐
+#[derive(Default)]
+pub struct MemoryStorage {
+    files: RwLock<HashMap<String, Vec<u8>>>,
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn put(&self, key: &str, content: &[u8]) -> io::Result<()> {
+        validate_key(key)?;
+
+        self.files
+            .write()
+            .map_err(|_| io::Error::other("memory storage lock poisoned"))?
+            .insert(key.to_string(), content.to_vec());
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>> {
+        validate_key(key)?;
+
+        self.files
+            .read()
+            .map_err(|_| io::Error::other("memory storage lock poisoned"))?
+            .get(key)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{key} not found")))
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        validate_key(key)?;
+
+        self.files
+            .write()
+            .map_err(|_| io::Error::other("memory storage lock poisoned"))?
+            .remove(key);
+
+        Ok(())
+    }
+}
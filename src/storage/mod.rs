@@ -0,0 +1,94 @@
+use std::{
+    io,
+    path::{Component, Path},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use crate::config::app::{StorageBackend, StorageConfig};
+
+pub mod local;
+pub mod memory;
+
+/// 업로드한 파일을 key로 저장하고 읽는 저장소. `storage.backend` 설정으로 구현을 고름.
+/// key는 `pets/7/<uuid>.jpg`처럼 `/`로 구분한 상대 경로이며 service가 만듦
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn put(&self, key: &str, content: &[u8]) -> io::Result<()>;
+
+    /// key에 해당하는 파일이 없으면 `io::ErrorKind::NotFound`를 반환함
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>>;
+
+    /// 이미 없는 파일을 지워도 오류가 아님
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+pub fn init(config: &StorageConfig) -> io::Result<Arc<dyn Storage>> {
+    let storage: Arc<dyn Storage> = match config.backend {
+        StorageBackend::Local => Arc::new(local::LocalStorage::new(&config.root)?),
+        StorageBackend::Memory => Arc::new(memory::MemoryStorage::default()),
+    };
+
+    Ok(storage)
+}
+
+/// 저장소 밖을 가리키지 않도록 빈 key, 절대 경로, `..`가 들어간 key를 거부함
+fn validate_key(key: &str) -> io::Result<()> {
+    let path = Path::new(key);
+    let is_valid = !key.is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if !is_valid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid storage key: {key}"),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{memory::MemoryStorage, *};
+
+    #[test]
+    fn test_validate_key() {
+        for key in ["pets/7/photo.jpg", "photo.jpg"] {
+            assert!(validate_key(key).is_ok(), "{key}");
+        }
+
+        for key in [
+            "",
+            "/etc/passwd",
+            "../photo.jpg",
+            "pets/../../photo.jpg",
+            "./photo.jpg",
+        ] {
+            assert!(validate_key(key).is_err(), "{key}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_memory_storage() {
+        let storage = MemoryStorage::default();
+
+        storage
+            .put("visits/1/report", b"lab results")
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get("visits/1/report").await.unwrap(),
+            b"lab results"
+        );
+
+        storage.delete("visits/1/report").await.unwrap();
+        let error = storage.get("visits/1/report").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        // 이미 지운 파일을 다시 지워도 오류가 아님
+        assert!(storage.delete("visits/1/report").await.is_ok());
+        assert!(storage.put("../report", b"").await.is_err());
+    }
+}
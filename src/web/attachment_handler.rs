@@ -0,0 +1,271 @@
+use actix_multipart::form::{bytes::Bytes, MultipartForm};
+use actix_web::{
+    get,
+    http::header::{
+        self, CacheControl, CacheDirective, Charset, ContentDisposition, DispositionParam,
+        DispositionType, ExtendedValue,
+    },
+    post, web, HttpRequest, HttpResponse,
+};
+use actix_web_flash_messages::FlashMessage;
+use sea_orm::DbConn;
+use serde::Deserialize;
+use tera::Context;
+use validator::ValidationErrors;
+
+use crate::{
+    config::app::AttachmentConfig,
+    model::{
+        app_error::AppError,
+        attachment::{sanitize_file_name, sniff_content_type},
+    },
+    service::{
+        attachment_service::{AttachmentService, NewAttachment},
+        owner_service::OwnerService,
+    },
+    web::{redirect, render, validator::create_validation_error},
+    AppState,
+};
+
+#[derive(Deserialize)]
+struct VisitPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+}
+
+#[derive(Deserialize)]
+struct AttachmentPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+    attachment_id: i32,
+}
+
+#[derive(MultipartForm)]
+struct AttachmentMultipartForm {
+    file: Option<Bytes>,
+}
+
+/// 파일을 고르지 않아도 browser는 빈 file part를 보내므로 내용이 없으면 required로 처리함.
+/// 형식은 browser가 보낸 content type이 아니라 파일 내용으로 판단함
+fn validate_attachment(
+    file: Option<Bytes>,
+    config: &AttachmentConfig,
+    errors: &mut ValidationErrors,
+) -> Option<NewAttachment> {
+    let Some(file) = file.filter(|file| !file.data.is_empty()) else {
+        errors.add("file", create_validation_error("required", "required"));
+        return None;
+    };
+
+    if file.data.len() > config.max_bytes {
+        errors.add("file", create_validation_error("too_large", "fileTooLarge"));
+        return None;
+    }
+
+    let file_name = sanitize_file_name(file.file_name.as_deref().unwrap_or_default());
+    let Some(content_type) = sniff_content_type(&file.data, &file_name) else {
+        errors.add(
+            "file",
+            create_validation_error("unsupported_type", "typeMismatch.attachment"),
+        );
+        return None;
+    };
+
+    Some(NewAttachment {
+        file_name,
+        content_type,
+        content: file.data.to_vec(),
+    })
+}
+
+async fn create_attachment_form_context(
+    conn: &DbConn,
+    config: &AttachmentConfig,
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+) -> Result<Context, AppError> {
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+
+    let pet = owner_with_pets
+        .pets_with_type
+        .iter()
+        .find(|p| p.pet_id == pet_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "pet".to_string(),
+            id: pet_id,
+        })?;
+    let visit = pet
+        .visits
+        .iter()
+        .find(|v| v.visit_id == visit_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "visit".to_string(),
+            id: visit_id,
+        })?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", pet);
+    ctx.insert("visit", visit);
+    ctx.insert("attachment_max_bytes", &config.max_bytes);
+    ctx.insert("current_menu", "owners");
+
+    Ok(ctx)
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/attachments/new")]
+pub async fn init_new_attachment_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let ctx = create_attachment_form_context(conn, &config.attachments, owner_id, pet_id, visit_id)
+        .await?;
+
+    render(tera, "pet/create-attachment-form.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/attachments/new")]
+pub async fn process_new_attachment_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+    MultipartForm(form): MultipartForm<AttachmentMultipartForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        storage,
+        ..
+    } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let mut errors = ValidationErrors::new();
+    let Some(attachment) = validate_attachment(form.file, &config.attachments, &mut errors) else {
+        let mut ctx =
+            create_attachment_form_context(conn, &config.attachments, owner_id, pet_id, visit_id)
+                .await?;
+        ctx.insert("errors", &i18n.translate_errors(&req, &errors));
+
+        return render(tera, "pet/create-attachment-form.html", ctx);
+    };
+
+    AttachmentService::save_attachment(
+        conn,
+        storage.as_ref(),
+        owner_id,
+        pet_id,
+        visit_id,
+        attachment,
+    )
+    .await?;
+
+    FlashMessage::info("File has been attached to the visit").send();
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
+
+/// 저장소를 직접 공개하지 않고, 첨부 파일이 path의 owner, pet, visit에 속하는지 확인한 뒤 내려줌.
+/// browser가 열지 않고 내려받도록 attachment로 응답함
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/attachments/{attachment_id:\d+}")]
+pub async fn download_attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<AttachmentPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, storage, .. } = app_state.get_ref();
+
+    let AttachmentPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+        attachment_id,
+    } = path.into_inner();
+
+    let attachment = AttachmentService::fetch_attachment_of_visit(
+        conn,
+        owner_id,
+        pet_id,
+        visit_id,
+        attachment_id,
+    )
+    .await?;
+    let content = AttachmentService::read_attachment(storage.as_ref(), &attachment).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type.as_str())
+        .insert_header(content_disposition(&attachment.file_name))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Private,
+            CacheDirective::NoCache,
+        ]))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(content))
+}
+
+/// ASCII가 아닌 파일 이름은 RFC 5987의 `filename*`로 보냄
+fn content_disposition(file_name: &str) -> ContentDisposition {
+    let parameter = if file_name.is_ascii() {
+        DispositionParam::Filename(file_name.to_string())
+    } else {
+        DispositionParam::FilenameExt(ExtendedValue {
+            charset: Charset::Ext("UTF-8".to_string()),
+            language_tag: None,
+            value: file_name.as_bytes().to_vec(),
+        })
+    };
+
+    ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![parameter],
+    }
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/attachments/{attachment_id:\d+}/delete")]
+pub async fn process_delete_attachment(
+    app_state: web::Data<AppState>,
+    path: web::Path<AttachmentPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, storage, .. } = app_state.get_ref();
+
+    let AttachmentPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+        attachment_id,
+    } = path.into_inner();
+
+    let attachment = AttachmentService::delete_attachment(
+        conn,
+        storage.as_ref(),
+        owner_id,
+        pet_id,
+        visit_id,
+        attachment_id,
+    )
+    .await?;
+
+    FlashMessage::info(format!("{} has been deleted", attachment.file_name)).send();
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
@@ -0,0 +1,457 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use chrono::{Local, NaiveDate};
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tera::Context;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::{
+    domain::owner::{billable_item::BillableItemKind, payment::PaymentMethod},
+    model::{app_error::AppError, money::Money},
+    service::{
+        billing_service::{BillingService, NewBillableItem, NewPayment},
+        owner_service::OwnerService,
+    },
+    web::{
+        extract_flash_messages, redirect, render,
+        validator::{
+            create_validation_error, validate_amount, validate_not_blank,
+            validate_today_or_past_date,
+        },
+    },
+    AppState,
+};
+
+#[derive(Deserialize)]
+struct VisitPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+}
+
+#[derive(Deserialize)]
+struct BillableItemPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+    item_id: i32,
+}
+
+#[derive(Deserialize)]
+struct InvoicePathParams {
+    owner_id: i32,
+    invoice_id: i32,
+}
+
+/// 잘못된 값도 form을 다시 보여주며 알려야 하므로 숫자와 선택 항목도 문자열로 받음
+#[derive(Serialize, Deserialize, Validate)]
+struct CreateBillableItemForm {
+    #[validate(custom(function = validate_item_kind))]
+    kind: String,
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 120, message = "120자를 넘을 수 없습니다")
+    )]
+    description: String,
+    #[validate(custom(function = validate_quantity))]
+    quantity: String,
+    #[validate(custom(function = validate_amount))]
+    unit_price: String,
+}
+
+impl CreateBillableItemForm {
+    fn kind(&self) -> Option<BillableItemKind> {
+        match self.kind.as_str() {
+            "service" => Some(BillableItemKind::Service),
+            "medication" => Some(BillableItemKind::Medication),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+struct CreatePaymentForm {
+    #[validate(custom(function = validate_today_or_past_date))]
+    paid_on: String,
+    #[validate(custom(function = validate_amount))]
+    amount: String,
+    #[validate(custom(function = validate_payment_method))]
+    method: String,
+    #[validate(length(max = 80, message = "80자를 넘을 수 없습니다"))]
+    reference: String,
+}
+
+impl CreatePaymentForm {
+    fn method(&self) -> Option<PaymentMethod> {
+        match self.method.as_str() {
+            "cash" => Some(PaymentMethod::Cash),
+            "card" => Some(PaymentMethod::Card),
+            "other" => Some(PaymentMethod::Other),
+            _ => None,
+        }
+    }
+}
+
+fn validate_item_kind(kind: &str) -> Result<(), ValidationError> {
+    if !matches!(kind, "service" | "medication") {
+        return Err(create_validation_error("unknown_kind", "notFound"));
+    }
+
+    Ok(())
+}
+
+fn validate_quantity(quantity: &str) -> Result<(), ValidationError> {
+    match quantity.trim().parse::<i32>() {
+        Ok(quantity) if (1..=1000).contains(&quantity) => Ok(()),
+        _ => Err(create_validation_error(
+            "range",
+            "1에서 1000 사이여야 합니다",
+        )),
+    }
+}
+
+fn validate_payment_method(method: &str) -> Result<(), ValidationError> {
+    if !matches!(method, "cash" | "card" | "other") {
+        return Err(create_validation_error("unknown_method", "notFound"));
+    }
+
+    Ok(())
+}
+
+/// visit 화면에 필요한 owner, pet, visit과 청구 항목을 담음. visit이 path의 owner와 pet에 속하지 않으면 not found임
+async fn create_visit_items_context(
+    conn: &DbConn,
+    currency: &str,
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+) -> Result<Context, AppError> {
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+
+    let pet = owner_with_pets
+        .pets_with_type
+        .iter()
+        .find(|p| p.pet_id == pet_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "pet".to_string(),
+            id: pet_id,
+        })?;
+    let visit = pet
+        .visits
+        .iter()
+        .find(|v| v.visit_id == visit_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "visit".to_string(),
+            id: visit_id,
+        })?;
+    let items = BillingService::fetch_items_by_visit(conn, visit_id).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", pet);
+    ctx.insert("visit", visit);
+    ctx.insert("items", &items);
+    ctx.insert("currency", currency);
+    ctx.insert("current_menu", "owners");
+
+    Ok(ctx)
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/items")]
+pub async fn show_visit_items(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let mut ctx =
+        create_visit_items_context(conn, &config.billing.currency, owner_id, pet_id, visit_id)
+            .await?;
+
+    let (success_message, error_message) = extract_flash_messages(&messages);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
+
+    render(tera, "invoice/visit-items.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/items")]
+pub async fn process_new_item(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+    form: web::Form<CreateBillableItemForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+    let item_form = form.into_inner();
+
+    if let Err(errors) = item_form.validate() {
+        let mut ctx =
+            create_visit_items_context(conn, &config.billing.currency, owner_id, pet_id, visit_id)
+                .await?;
+        ctx.insert("item", &item_form);
+        ctx.insert("errors", &i18n.translate_errors(&req, &errors));
+
+        return render(tera, "invoice/visit-items.html", ctx);
+    }
+
+    // form data 검증 시 확인하였으므로 모든 값을 해석할 수 있음
+    let new_item = NewBillableItem {
+        kind: item_form.kind().unwrap(),
+        description: item_form.description.trim().to_string(),
+        quantity: item_form.quantity.trim().parse().unwrap(),
+        unit_price: Money::parse(&item_form.unit_price).unwrap(),
+    };
+    BillingService::add_item(conn, owner_id, pet_id, visit_id, new_item).await?;
+
+    FlashMessage::info("Item has been added").send();
+
+    Ok(redirect(format!(
+        "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/items"
+    )))
+}
+
+#[post(
+    r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/items/{item_id:\d+}/delete"
+)]
+pub async fn process_delete_item(
+    app_state: web::Data<AppState>,
+    path: web::Path<BillableItemPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let BillableItemPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+        item_id,
+    } = path.into_inner();
+
+    match BillingService::delete_item(conn, owner_id, pet_id, visit_id, item_id).await {
+        Ok(()) => FlashMessage::info("Item has been deleted").send(),
+        Err(AppError::InvalidResourceState { .. }) => {
+            FlashMessage::error("Item is already on an invoice and cannot be deleted").send()
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect(format!(
+        "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/items"
+    )))
+}
+
+#[get(r"/owners/{owner_id:\d+}/invoices")]
+pub async fn show_invoice_list(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let owner_id = path.into_inner();
+
+    let owner = OwnerService::fetch_owner_by_id(conn, owner_id).await?;
+    let invoices = BillingService::fetch_invoices_by_owner(conn, owner_id).await?;
+    let unbilled_items = BillingService::fetch_unbilled_items_by_owner(conn, owner_id).await?;
+    let unbilled_total: Money = unbilled_items.iter().map(|item| item.amount).sum();
+
+    let (success_message, error_message) = extract_flash_messages(&messages);
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner);
+    ctx.insert("invoices", &invoices);
+    ctx.insert("unbilled_items", &unbilled_items);
+    ctx.insert("unbilled_total", &unbilled_total);
+    ctx.insert("currency", &config.billing.currency);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
+    ctx.insert("current_menu", "owners");
+
+    render(tera, "invoice/invoice-list.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/invoices/new")]
+pub async fn process_create_invoice(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, config, .. } = app_state.get_ref();
+
+    let owner_id = path.into_inner();
+
+    match BillingService::create_invoice(conn, owner_id, config.billing.tax_rate_basis_points).await
+    {
+        Ok(invoice) => {
+            FlashMessage::info("Invoice has been issued").send();
+
+            Ok(redirect(format!(
+                "/owners/{owner_id}/invoices/{}",
+                invoice.id
+            )))
+        }
+        Err(AppError::InvalidResourceState { .. }) => {
+            FlashMessage::error("There are no unbilled items to invoice").send();
+
+            Ok(redirect(format!("/owners/{owner_id}/invoices")))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn create_invoice_context(
+    conn: &DbConn,
+    currency: &str,
+    owner_id: i32,
+    invoice_id: i32,
+) -> Result<Context, AppError> {
+    let owner = OwnerService::fetch_owner_by_id(conn, owner_id).await?;
+    let invoice = BillingService::fetch_invoice(conn, owner_id, invoice_id).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner);
+    ctx.insert("invoice", &invoice);
+    ctx.insert("today", &Local::now().date_naive());
+    ctx.insert("currency", currency);
+    ctx.insert("current_menu", "owners");
+
+    Ok(ctx)
+}
+
+#[get(r"/owners/{owner_id:\d+}/invoices/{invoice_id:\d+}")]
+pub async fn show_invoice(
+    app_state: web::Data<AppState>,
+    path: web::Path<InvoicePathParams>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let InvoicePathParams {
+        owner_id,
+        invoice_id,
+    } = path.into_inner();
+
+    let mut ctx =
+        create_invoice_context(conn, &config.billing.currency, owner_id, invoice_id).await?;
+
+    let (success_message, error_message) = extract_flash_messages(&messages);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
+
+    render(tera, "invoice/invoice-details.html", ctx)
+}
+
+fn exceeds_balance_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "amount",
+        create_validation_error("exceeds_balance", "exceedsBalance"),
+    );
+
+    errors
+}
+
+#[post(r"/owners/{owner_id:\d+}/invoices/{invoice_id:\d+}/payments")]
+pub async fn process_new_payment(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<InvoicePathParams>,
+    form: web::Form<CreatePaymentForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state.get_ref();
+
+    let InvoicePathParams {
+        owner_id,
+        invoice_id,
+    } = path.into_inner();
+    let payment_form = form.into_inner();
+
+    let mut errors = payment_form.validate().err().unwrap_or_default();
+    if Money::parse(&payment_form.amount) == Some(Money::ZERO) {
+        errors.add(
+            "amount",
+            create_validation_error("positive_amount", "typeMismatch.amount"),
+        );
+    }
+
+    if errors.is_empty() {
+        // form data 검증 시 확인하였으므로 날짜, 금액, 결제 수단은 반드시 해석할 수 있음
+        let new_payment = NewPayment {
+            paid_on: NaiveDate::parse_from_str(&payment_form.paid_on, "%Y-%m-%d").unwrap(),
+            amount: Money::parse(&payment_form.amount).unwrap(),
+            method: payment_form.method().unwrap(),
+            reference: Some(payment_form.reference.trim().to_string()).filter(|r| !r.is_empty()),
+        };
+
+        match BillingService::record_payment(conn, owner_id, invoice_id, new_payment).await {
+            Ok(_) => {
+                FlashMessage::info("Payment has been recorded").send();
+
+                return Ok(redirect(format!(
+                    "/owners/{owner_id}/invoices/{invoice_id}"
+                )));
+            }
+            Err(AppError::InvalidResourceState { .. }) => errors = exceeds_balance_errors(),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut ctx =
+        create_invoice_context(conn, &config.billing.currency, owner_id, invoice_id).await?;
+    ctx.insert("payment", &payment_form);
+    ctx.insert("errors", &i18n.translate_errors(&req, &errors));
+
+    render(tera, "invoice/invoice-details.html", ctx)
+}
+
+#[get(r"/owners/{owner_id:\d+}/invoices/{invoice_id:\d+}/print")]
+pub async fn print_invoice(
+    app_state: web::Data<AppState>,
+    path: web::Path<InvoicePathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let InvoicePathParams {
+        owner_id,
+        invoice_id,
+    } = path.into_inner();
+
+    let ctx = create_invoice_context(conn, &config.billing.currency, owner_id, invoice_id).await?;
+
+    render(tera, "invoice/print-invoice.html", ctx)
+}
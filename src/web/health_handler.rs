@@ -0,0 +1,73 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use actix_web::{get, web, HttpResponse};
+use sea_orm::ConnectionTrait;
+use tokio::time::timeout;
+
+use crate::{
+    model::health_response::{ComponentHealth, HealthResponse},
+    AppState,
+};
+
+// pool이 고갈되면 acquire timeout(기본 30초)까지 기다리므로 probe가 먼저 포기하도록 함
+const DB_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[get("/health/live")]
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(HealthResponse::new(BTreeMap::new()))
+}
+
+#[get("/health/ready")]
+pub async fn ready(app_state: web::Data<AppState>) -> HttpResponse {
+    let AppState {
+        conn, tera, i18n, ..
+    } = app_state.get_ref();
+
+    let mut components = BTreeMap::new();
+
+    let started = Instant::now();
+    let result = match timeout(DB_PING_TIMEOUT, conn.ping()).await {
+        Ok(Ok(())) => Ok(format!("{:?}", conn.get_database_backend())),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!("no response within {DB_PING_TIMEOUT:?}")),
+    };
+    components.insert(
+        "database",
+        ComponentHealth::new(result, elapsed_ms(started)),
+    );
+
+    let started = Instant::now();
+    let template_count = tera.get_template_names().count();
+    let result = if template_count > 0 {
+        Ok(format!("{template_count} templates loaded"))
+    } else {
+        Err("no templates loaded".to_string())
+    };
+    components.insert(
+        "templates",
+        ComponentHealth::new(result, elapsed_ms(started)),
+    );
+
+    let started = Instant::now();
+    let missing = i18n.missing_languages();
+    let result = if missing.is_empty() {
+        Ok(format!("{} languages loaded", i18n.language_count()))
+    } else {
+        Err(format!("missing translations for {}", missing.join(", ")))
+    };
+    components.insert("i18n", ComponentHealth::new(result, elapsed_ms(started)));
+
+    let health = HealthResponse::new(components);
+    if health.is_up() {
+        HttpResponse::Ok().json(health)
+    } else {
+        HttpResponse::ServiceUnavailable().json(health)
+    }
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
@@ -0,0 +1,15 @@
+use actix_web::{get, web, HttpResponse};
+
+use crate::{config::metrics::METRICS, AppState};
+
+#[get("/metrics")]
+pub async fn metrics(app_state: web::Data<AppState>) -> HttpResponse {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    match METRICS.render(conn) {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(body),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
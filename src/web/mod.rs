@@ -1,24 +1,446 @@
+use ::validator::ValidationErrors;
 use actix_web::{
     http::{self, header::ContentType},
     web::ServiceConfig,
     HttpResponse,
 };
+use actix_web_flash_messages::{IncomingFlashMessages, Level};
 use tera::{Context, Tera};
 
-use crate::model::app_error::AppError;
+use crate::{
+    config::app::PaginationConfig, model::app_error::AppError,
+    web::validator::create_validation_error,
+};
 
+pub mod attachment_handler;
+pub mod billing_handler;
 pub mod error_handler;
+pub mod health_handler;
+pub mod metrics_handler;
 pub mod owner_handler;
 pub mod pet_handler;
+pub mod pet_type_handler;
+pub mod specialty_handler;
+pub mod vaccination_handler;
 pub mod validator;
 pub mod vet_handler;
 pub mod visit_handler;
 pub mod welcome_handler;
 
+pub struct Route {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub handler: &'static str,
+}
+
+/// `configure_route`에 등록된 route 목록. route를 추가하면 함께 갱신해야 함
+pub const ROUTES: &[Route] = &[
+    Route {
+        method: "GET",
+        path: "/",
+        handler: "welcome_handler::welcome",
+    },
+    Route {
+        method: "GET",
+        path: "/vets",
+        handler: "vet_handler::show_resources_vet_list",
+    },
+    Route {
+        method: "GET",
+        path: "/vets.html",
+        handler: "vet_handler::show_vet_list",
+    },
+    Route {
+        method: "GET",
+        path: "/vets/{vet_id}/visits",
+        handler: "vet_handler::show_vet_visits",
+    },
+    Route {
+        method: "GET",
+        path: "/vets/new",
+        handler: "vet_handler::init_creation_form",
+    },
+    Route {
+        method: "POST",
+        path: "/vets/new",
+        handler: "vet_handler::process_creation_form",
+    },
+    Route {
+        method: "GET",
+        path: "/vets/{vet_id}/edit",
+        handler: "vet_handler::init_update_form",
+    },
+    Route {
+        method: "POST",
+        path: "/vets/{vet_id}/edit",
+        handler: "vet_handler::process_update_form",
+    },
+    Route {
+        method: "POST",
+        path: "/vets/{vet_id}/retire",
+        handler: "vet_handler::process_retire_vet",
+    },
+    Route {
+        method: "POST",
+        path: "/vets/{vet_id}/reinstate",
+        handler: "vet_handler::process_reinstate_vet",
+    },
+    Route {
+        method: "GET",
+        path: "/specialties",
+        handler: "specialty_handler::show_specialty_list",
+    },
+    Route {
+        method: "GET",
+        path: "/specialties/new",
+        handler: "specialty_handler::init_creation_form",
+    },
+    Route {
+        method: "POST",
+        path: "/specialties/new",
+        handler: "specialty_handler::process_creation_form",
+    },
+    Route {
+        method: "GET",
+        path: "/specialties/{specialty_id}/edit",
+        handler: "specialty_handler::init_update_form",
+    },
+    Route {
+        method: "POST",
+        path: "/specialties/{specialty_id}/edit",
+        handler: "specialty_handler::process_update_form",
+    },
+    Route {
+        method: "GET",
+        path: "/specialties/{specialty_id}/delete",
+        handler: "specialty_handler::init_delete_form",
+    },
+    Route {
+        method: "POST",
+        path: "/specialties/{specialty_id}/delete",
+        handler: "specialty_handler::process_delete_form",
+    },
+    Route {
+        method: "GET",
+        path: "/api/specialties",
+        handler: "specialty_handler::list_specialties",
+    },
+    Route {
+        method: "GET",
+        path: "/api/specialties/{specialty_id}",
+        handler: "specialty_handler::get_specialty",
+    },
+    Route {
+        method: "POST",
+        path: "/api/specialties",
+        handler: "specialty_handler::create_specialty",
+    },
+    Route {
+        method: "PUT",
+        path: "/api/specialties/{specialty_id}",
+        handler: "specialty_handler::update_specialty",
+    },
+    Route {
+        method: "DELETE",
+        path: "/api/specialties/{specialty_id}",
+        handler: "specialty_handler::delete_specialty",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}",
+        handler: "owner_handler::show_owner",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/new",
+        handler: "owner_handler::init_creation_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/new",
+        handler: "owner_handler::process_creation_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/find",
+        handler: "owner_handler::init_find_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners",
+        handler: "owner_handler::process_find_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/edit",
+        handler: "owner_handler::init_update_owner_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/edit",
+        handler: "owner_handler::process_update_owner_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/delete",
+        handler: "owner_handler::init_delete_owner_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/delete",
+        handler: "owner_handler::process_delete_owner_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/new",
+        handler: "pet_handler::init_creation_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/new",
+        handler: "pet_handler::process_creation_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/edit",
+        handler: "pet_handler::init_update_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/edit",
+        handler: "pet_handler::process_update_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/photo",
+        handler: "pet_handler::show_photo",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/photo/thumbnail",
+        handler: "pet_handler::show_photo_thumbnail",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/delete",
+        handler: "pet_handler::init_delete_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/delete",
+        handler: "pet_handler::process_delete_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/transfer",
+        handler: "pet_handler::init_transfer_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/transfer/{new_owner_id}",
+        handler: "pet_handler::init_confirm_transfer_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/transfer/{new_owner_id}",
+        handler: "pet_handler::process_transfer_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/new",
+        handler: "visit_handler::init_new_visit_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/new",
+        handler: "visit_handler::process_new_visit_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/edit",
+        handler: "visit_handler::init_update_visit_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/edit",
+        handler: "visit_handler::process_update_visit_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/cancel",
+        handler: "visit_handler::process_cancel_visit",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/delete",
+        handler: "visit_handler::init_delete_visit_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/delete",
+        handler: "visit_handler::process_delete_visit_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/prescriptions/print",
+        handler: "visit_handler::print_prescriptions",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/attachments/new",
+        handler: "attachment_handler::init_new_attachment_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/attachments/new",
+        handler: "attachment_handler::process_new_attachment_form",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/attachments/{attachment_id}",
+        handler: "attachment_handler::download_attachment",
+    },
+    Route {
+        method: "POST",
+        path:
+            "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/attachments/{attachment_id}/delete",
+        handler: "attachment_handler::process_delete_attachment",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/items",
+        handler: "billing_handler::show_visit_items",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/items",
+        handler: "billing_handler::process_new_item",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/visits/{visit_id}/items/{item_id}/delete",
+        handler: "billing_handler::process_delete_item",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/invoices",
+        handler: "billing_handler::show_invoice_list",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/invoices/new",
+        handler: "billing_handler::process_create_invoice",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/invoices/{invoice_id}",
+        handler: "billing_handler::show_invoice",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/invoices/{invoice_id}/payments",
+        handler: "billing_handler::process_new_payment",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/invoices/{invoice_id}/print",
+        handler: "billing_handler::print_invoice",
+    },
+    Route {
+        method: "GET",
+        path: "/owners/{owner_id}/pets/{pet_id}/vaccinations/new",
+        handler: "vaccination_handler::init_new_vaccination_form",
+    },
+    Route {
+        method: "POST",
+        path: "/owners/{owner_id}/pets/{pet_id}/vaccinations/new",
+        handler: "vaccination_handler::process_new_vaccination_form",
+    },
+    Route {
+        method: "GET",
+        path: "/vaccinations/due",
+        handler: "vaccination_handler::show_due_vaccinations",
+    },
+    Route {
+        method: "GET",
+        path: "/pet-types",
+        handler: "pet_type_handler::show_pet_type_list",
+    },
+    Route {
+        method: "GET",
+        path: "/pet-types/new",
+        handler: "pet_type_handler::init_creation_form",
+    },
+    Route {
+        method: "POST",
+        path: "/pet-types/new",
+        handler: "pet_type_handler::process_creation_form",
+    },
+    Route {
+        method: "GET",
+        path: "/pet-types/{pet_type_id}/edit",
+        handler: "pet_type_handler::init_update_form",
+    },
+    Route {
+        method: "POST",
+        path: "/pet-types/{pet_type_id}/edit",
+        handler: "pet_type_handler::process_update_form",
+    },
+    Route {
+        method: "GET",
+        path: "/pet-types/{pet_type_id}/delete",
+        handler: "pet_type_handler::init_delete_form",
+    },
+    Route {
+        method: "POST",
+        path: "/pet-types/{pet_type_id}/delete",
+        handler: "pet_type_handler::process_delete_form",
+    },
+    Route {
+        method: "GET",
+        path: "/oups",
+        handler: "error_handler::trigger_error",
+    },
+    Route {
+        method: "GET",
+        path: "/health/live",
+        handler: "health_handler::live",
+    },
+    Route {
+        method: "GET",
+        path: "/health/ready",
+        handler: "health_handler::ready",
+    },
+    Route {
+        method: "GET",
+        path: "/metrics",
+        handler: "metrics_handler::metrics",
+    },
+];
+
 pub fn configure_route(cfg: &mut ServiceConfig) {
     cfg.service(welcome_handler::welcome)
         .service(vet_handler::show_resources_vet_list)
         .service(vet_handler::show_vet_list)
+        .service(vet_handler::show_vet_visits)
+        .service(vet_handler::init_creation_form)
+        .service(vet_handler::process_creation_form)
+        .service(vet_handler::init_update_form)
+        .service(vet_handler::process_update_form)
+        .service(vet_handler::process_retire_vet)
+        .service(vet_handler::process_reinstate_vet)
+        .service(specialty_handler::show_specialty_list)
+        .service(specialty_handler::init_creation_form)
+        .service(specialty_handler::process_creation_form)
+        .service(specialty_handler::init_update_form)
+        .service(specialty_handler::process_update_form)
+        .service(specialty_handler::init_delete_form)
+        .service(specialty_handler::process_delete_form)
+        .service(specialty_handler::list_specialties)
+        .service(specialty_handler::get_specialty)
+        .service(specialty_handler::create_specialty)
+        .service(specialty_handler::update_specialty)
+        .service(specialty_handler::delete_specialty)
         .service(owner_handler::show_owner)
         .service(owner_handler::init_creation_form)
         .service(owner_handler::process_creation_form)
@@ -26,13 +448,53 @@ pub fn configure_route(cfg: &mut ServiceConfig) {
         .service(owner_handler::process_find_form)
         .service(owner_handler::init_update_owner_form)
         .service(owner_handler::process_update_owner_form)
+        .service(owner_handler::init_delete_owner_form)
+        .service(owner_handler::process_delete_owner_form)
         .service(pet_handler::init_creation_form)
         .service(pet_handler::process_creation_form)
         .service(pet_handler::init_update_form)
         .service(pet_handler::process_update_form)
+        .service(pet_handler::show_photo)
+        .service(pet_handler::show_photo_thumbnail)
+        .service(pet_handler::init_delete_form)
+        .service(pet_handler::process_delete_form)
+        .service(pet_handler::init_transfer_form)
+        .service(pet_handler::init_confirm_transfer_form)
+        .service(pet_handler::process_transfer_form)
         .service(visit_handler::init_new_visit_form)
         .service(visit_handler::process_new_visit_form)
-        .service(error_handler::trigger_error);
+        .service(visit_handler::init_update_visit_form)
+        .service(visit_handler::process_update_visit_form)
+        .service(visit_handler::process_cancel_visit)
+        .service(visit_handler::init_delete_visit_form)
+        .service(visit_handler::process_delete_visit_form)
+        .service(visit_handler::print_prescriptions)
+        .service(attachment_handler::init_new_attachment_form)
+        .service(attachment_handler::process_new_attachment_form)
+        .service(attachment_handler::download_attachment)
+        .service(attachment_handler::process_delete_attachment)
+        .service(billing_handler::show_visit_items)
+        .service(billing_handler::process_new_item)
+        .service(billing_handler::process_delete_item)
+        .service(billing_handler::show_invoice_list)
+        .service(billing_handler::process_create_invoice)
+        .service(billing_handler::show_invoice)
+        .service(billing_handler::process_new_payment)
+        .service(billing_handler::print_invoice)
+        .service(vaccination_handler::init_new_vaccination_form)
+        .service(vaccination_handler::process_new_vaccination_form)
+        .service(vaccination_handler::show_due_vaccinations)
+        .service(pet_type_handler::show_pet_type_list)
+        .service(pet_type_handler::init_creation_form)
+        .service(pet_type_handler::process_creation_form)
+        .service(pet_type_handler::init_update_form)
+        .service(pet_type_handler::process_update_form)
+        .service(pet_type_handler::init_delete_form)
+        .service(pet_type_handler::process_delete_form)
+        .service(error_handler::trigger_error)
+        .service(health_handler::live)
+        .service(health_handler::ready)
+        .service(metrics_handler::metrics);
 }
 
 pub fn render(
@@ -53,3 +515,241 @@ pub fn redirect(path: String) -> HttpResponse {
         .append_header((http::header::LOCATION, path))
         .finish()
 }
+
+/// page는 1부터, size는 1부터 max_page_size까지만 받음. 벗어나면 400으로 응답함
+pub fn page_and_size(
+    page: Option<u64>,
+    size: Option<u64>,
+    pagination: &PaginationConfig,
+) -> Result<(u64, u64), AppError> {
+    let (page, size) = (page.unwrap_or(1), size.unwrap_or(pagination.page_size));
+
+    let mut errors = ValidationErrors::new();
+    if page == 0 {
+        errors.add("page", create_validation_error("range", "range"));
+    }
+    if size == 0 || size > pagination.max_page_size {
+        errors.add("size", create_validation_error("range", "range"));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::InvalidRequest(errors));
+    }
+
+    Ok((page, size))
+}
+
+pub fn extract_flash_messages(messages: &IncomingFlashMessages) -> (Option<&str>, Option<&str>) {
+    let (mut success_message, mut error_message) = (None, None);
+
+    for message in messages.iter() {
+        match message.level() {
+            Level::Info => success_message = Some(message.content()),
+            Level::Error => error_message = Some(message.content()),
+            _ => {}
+        }
+    }
+
+    (success_message, error_message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use actix_web::{
+        cookie::Key,
+        http::{Method, StatusCode},
+        test,
+        web::Data,
+        App,
+    };
+    use actix_web_flash_messages::FlashMessagesFramework;
+    use regex::Regex;
+    use sea_orm::{sqlx::SqlitePool, DbConn, SqlxSqliteConnector};
+
+    use super::*;
+    use crate::{
+        config::{
+            app::{AppConfig, StorageBackend, StorageConfig},
+            cookie::SigningKeys,
+            db::connect_test_db,
+            i18n::{I18n, DEFAULT_LANGUAGE},
+            tera,
+        },
+        service::billing_service::BillingService,
+        storage, AppState,
+    };
+
+    fn sample_path(path: &str) -> String {
+        path.split('/')
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    "1"
+                } else {
+                    segment
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn test_app_state(conn: DbConn) -> AppState {
+        AppState {
+            conn,
+            tera: Tera::default(),
+            i18n: I18n::try_new("locales", DEFAULT_LANGUAGE).unwrap(),
+            config: AppConfig::default(),
+            storage: storage::init(&StorageConfig {
+                backend: StorageBackend::Memory,
+                ..StorageConfig::default()
+            })
+            .unwrap(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_routes_are_registered() {
+        let app_state = test_app_state(SqlxSqliteConnector::from_sqlx_sqlite_pool(
+            SqlitePool::connect_lazy("sqlite::memory:").unwrap(),
+        ));
+        // flash message를 읽는 handler가 있으므로 start_server와 같이 framework로 감쌈
+        let signing_keys = SigningKeys {
+            primary: Key::generate(),
+            fallback: None,
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(app_state))
+                .wrap(FlashMessagesFramework::builder(signing_keys.message_store()).build())
+                .configure(configure_route),
+        )
+        .await;
+
+        for route in ROUTES {
+            let req = test::TestRequest::default()
+                .method(Method::from_bytes(route.method.as_bytes()).unwrap())
+                .uri(&sample_path(route.path))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+
+            assert_ne!(
+                res.status(),
+                StatusCode::NOT_FOUND,
+                "{} {} is not registered",
+                route.method,
+                route.path
+            );
+        }
+    }
+
+    /// handler 파일의 route attribute를 모두 찾아 ROUTES에 빠진 route가 없는지 확인함
+    #[actix_web::test]
+    async fn test_routes_list_every_handler() {
+        let attribute = Regex::new(
+            r#"#\[(get|post|put|delete|patch)\(\s*r?"([^"]+)"\s*\)\]\s*pub async fn (\w+)"#,
+        )
+        .unwrap();
+        let path_regex = Regex::new(r"\{(\w+):[^}]*\}").unwrap();
+
+        let mut handler_count = 0;
+        for entry in fs::read_dir("src/web").unwrap() {
+            let path = entry.unwrap().path();
+            let Some(module) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| stem.ends_with("_handler"))
+            else {
+                continue;
+            };
+            let source = fs::read_to_string(&path).unwrap();
+
+            for captures in attribute.captures_iter(&source) {
+                let method = captures[1].to_uppercase();
+                let route_path = path_regex.replace_all(&captures[2], "{$1}");
+                let handler = format!("{module}::{}", &captures[3]);
+                handler_count += 1;
+
+                assert!(
+                    ROUTES.iter().any(|route| route.method == method
+                        && route.path == route_path
+                        && route.handler == handler),
+                    "{method} {route_path} ({handler}) is missing from ROUTES"
+                );
+            }
+        }
+
+        assert_eq!(handler_count, ROUTES.len(), "ROUTES has unknown routes");
+    }
+
+    #[actix_web::test]
+    async fn test_page_out_of_range_is_bad_request() {
+        let signing_keys = SigningKeys {
+            primary: Key::generate(),
+            fallback: None,
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(test_app_state(connect_test_db().await)))
+                .wrap(FlashMessagesFramework::builder(signing_keys.message_store()).build())
+                .configure(configure_route),
+        )
+        .await;
+
+        for uri in [
+            "/vets/1/visits?page=0",
+            "/vets/1/visits?size=0",
+            "/vets/1/visits?size=101",
+            "/vets.html?page=0",
+            "/owners?last_name=D&page=0",
+            "/owners/6/pets/7/transfer?last_name=D&page=0",
+        ] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let res = test::call_service(&app, req).await;
+
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{uri}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_bad_item_quantity_shows_form_error() {
+        let conn = connect_test_db().await;
+        let mut app_state = test_app_state(conn.clone());
+        app_state.tera = tera::try_init(&app_state.config.paths).unwrap();
+        let signing_keys = SigningKeys {
+            primary: Key::generate(),
+            fallback: None,
+        };
+        let app = test::init_service(
+            App::new()
+                .app_data(Data::new(app_state))
+                .wrap(FlashMessagesFramework::builder(signing_keys.message_store()).build())
+                .configure(configure_route),
+        )
+        .await;
+
+        // visit 4는 owner 6의 pet 7에 속함
+        for form in [
+            "kind=service&description=Exam&quantity=abc&unit_price=10.00",
+            "kind=grooming&description=Exam&quantity=1&unit_price=10.00",
+        ] {
+            let req = test::TestRequest::post()
+                .uri("/owners/6/pets/7/visits/4/items")
+                .insert_header(ContentType::form_url_encoded())
+                .set_payload(form)
+                .to_request();
+            let res = test::call_service(&app, req).await;
+
+            assert_eq!(res.status(), StatusCode::OK, "{form}");
+            let body = test::read_body(res).await;
+            assert!(
+                String::from_utf8_lossy(&body).contains("has-error"),
+                "{form}"
+            );
+        }
+
+        let items = BillingService::fetch_items_by_visit(&conn, 4)
+            .await
+            .unwrap();
+        assert!(items.is_empty());
+    }
+}
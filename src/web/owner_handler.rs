@@ -1,41 +1,69 @@
-use std::sync::LazyLock;
+use std::{borrow::Cow, sync::LazyLock};
 
 use actix_web::{get, post, web, HttpRequest, HttpResponse};
-use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages, Level};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use tera::Context;
-use validator::Validate;
+use validator::{Validate, ValidateEmail, ValidationError, ValidationErrors, ValidationErrorsKind};
 
 use crate::{
     config::i18n::I18n,
-    model::{app_error::AppError, page::Page},
-    service::owner_service::OwnerService,
-    web::{redirect, render},
+    domain::owner::{
+        owner_phone::{self, PhoneKind},
+        owners::{self, ContactMethod},
+    },
+    model::{app_error::AppError, page::Page, phone::normalize_phone},
+    service::{
+        attachment_service::AttachmentService,
+        billing_service::BillingService,
+        owner_service::{ContactDetails, OwnerService},
+        pet_service::PetService,
+        vaccination_service::VaccinationService,
+        visit_service::VisitService,
+    },
+    web::{
+        extract_flash_messages, page_and_size, redirect, render,
+        validator::{create_validation_error, split_form_rows},
+    },
     AppState,
 };
 
-static PHONE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\d{10}$").unwrap());
 static NUMERIC_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:\d+)?$").unwrap());
 
 #[get(r"/owners/{owner_id:\d+}")]
 pub async fn show_owner(
     app_state: web::Data<AppState>,
-    path: web::Path<u32>,
+    path: web::Path<i32>,
     messages: IncomingFlashMessages,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
     let owner_id = path.into_inner();
 
     let owner_with_pets_and_types_and_visits =
         OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
-            .await?;
+            .await?
+            .with_balance(BillingService::fetch_owner_balance(conn, owner_id).await?)
+            .with_prescriptions(VisitService::fetch_prescriptions_by_owner(conn, owner_id).await?)
+            .with_attachments(AttachmentService::fetch_attachments_by_owner(conn, owner_id).await?);
+    let transfers = PetService::fetch_transfers_by_owner(conn, owner_id).await?;
+    let vaccinations = VaccinationService::fetch_vaccinations_by_owner(
+        conn,
+        owner_id,
+        config.vaccinations.due_soon_days,
+    )
+    .await?;
 
     let (success_message, error_message) = extract_flash_messages(&messages);
 
     let mut ctx = Context::new();
     ctx.insert("owner", &owner_with_pets_and_types_and_visits);
+    ctx.insert("transfers", &transfers);
+    ctx.insert("vaccinations", &vaccinations);
+    ctx.insert("currency", &config.billing.currency);
     ctx.insert("success_message", &success_message);
     ctx.insert("error_message", &error_message);
     ctx.insert("current_menu", "owners");
@@ -43,20 +71,6 @@ pub async fn show_owner(
     render(tera, "owner/owner-details.html", ctx)
 }
 
-fn extract_flash_messages(messages: &IncomingFlashMessages) -> (Option<&str>, Option<&str>) {
-    let (mut success_message, mut error_message) = (None, None);
-
-    for message in messages.iter() {
-        match message.level() {
-            Level::Info => success_message = Some(message.content()),
-            Level::Error => error_message = Some(message.content()),
-            _ => {}
-        }
-    }
-
-    (success_message, error_message)
-}
-
 #[get("/owners/new")]
 pub async fn init_creation_form(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
     let AppState { tera, .. } = app_state.get_ref();
@@ -67,7 +81,7 @@ pub async fn init_creation_form(app_state: web::Data<AppState>) -> Result<HttpRe
     render(tera, "owner/create-or-update-owner-form.html", ctx)
 }
 
-#[derive(Serialize, Deserialize, Validate)]
+#[derive(Default, Serialize, Validate)]
 struct CreateOrUpdateOwnerForm {
     #[validate(length(min = 1, message = "공백일 수 없습니다"))]
     first_name: String,
@@ -77,34 +91,257 @@ struct CreateOrUpdateOwnerForm {
     address: String,
     #[validate(length(min = 1, message = "공백일 수 없습니다"))]
     city: String,
-    #[validate(length(min = 1, message = "공백일 수 없습니다"))]
-    #[validate(regex(path = *PHONE_REGEX, message = "Telephone must be a 10-digit number"))]
-    telephone: String,
+    #[validate(
+        custom(function = validate_optional_email),
+        length(max = 120, message = "120자를 넘을 수 없습니다")
+    )]
+    email: String,
+    /// 각 줄은 validate_with에서 따로 검증함
+    phones: Vec<PhoneForm>,
+    preferred_contact: String,
+    #[validate(length(max = 80, message = "80자를 넘을 수 없습니다"))]
+    emergency_contact_name: String,
+    emergency_contact_phone: String,
     #[validate(regex(path = *NUMERIC_REGEX))]
     id: String,
 }
 
+#[derive(Default, Serialize)]
+struct PhoneForm {
+    kind: String,
+    number: String,
+}
+
+impl PhoneForm {
+    fn kind(&self) -> Option<PhoneKind> {
+        match self.kind.as_str() {
+            "mobile" => Some(PhoneKind::Mobile),
+            "home" => Some(PhoneKind::Home),
+            "work" => Some(PhoneKind::Work),
+            _ => None,
+        }
+    }
+}
+
+fn validate_optional_email(email: &str) -> Result<(), ValidationError> {
+    if !email.is_empty() && !email.validate_email() {
+        return Err(create_validation_error("email", "typeMismatch.email"));
+    }
+
+    Ok(())
+}
+
+impl CreateOrUpdateOwnerForm {
+    /// 전화번호 줄은 같은 key를 여러 번 보내므로 key-value 쌍에서 읽음.
+    /// 한 줄은 phone_kind부터 시작하며, 번호를 입력하지 않은 줄은 버림
+    fn from_pairs(pairs: Vec<(String, String)>) -> Self {
+        let (fields, rows) = split_form_rows(pairs, "phone_", "kind");
+
+        let mut owner_form = Self::default();
+        for (key, value) in fields {
+            match key.as_str() {
+                "first_name" => owner_form.first_name = value,
+                "last_name" => owner_form.last_name = value,
+                "address" => owner_form.address = value,
+                "city" => owner_form.city = value,
+                "email" => owner_form.email = value.trim().to_string(),
+                "preferred_contact" => owner_form.preferred_contact = value,
+                "emergency_contact_name" => owner_form.emergency_contact_name = value,
+                "emergency_contact_phone" => owner_form.emergency_contact_phone = value,
+                "id" => owner_form.id = value,
+                _ => {}
+            }
+        }
+        owner_form.phones = rows
+            .into_iter()
+            .map(|mut row| PhoneForm {
+                kind: row.take("kind"),
+                number: row.take("number"),
+            })
+            .filter(|phone| !phone.number.trim().is_empty())
+            .collect();
+
+        owner_form
+    }
+
+    fn from_model(owner: owners::Model, phones: Vec<owner_phone::Model>) -> Self {
+        CreateOrUpdateOwnerForm {
+            first_name: owner.first_name.unwrap_or_default(),
+            last_name: owner.last_name.unwrap_or_default(),
+            address: owner.address.unwrap_or_default(),
+            city: owner.city.unwrap_or_default(),
+            email: owner.email.unwrap_or_default(),
+            phones: phones
+                .into_iter()
+                .map(|phone| PhoneForm {
+                    kind: match phone.kind {
+                        PhoneKind::Mobile => "mobile",
+                        PhoneKind::Home => "home",
+                        PhoneKind::Work => "work",
+                    }
+                    .to_string(),
+                    number: phone.number,
+                })
+                .collect(),
+            preferred_contact: match owner.preferred_contact {
+                ContactMethod::Phone => "phone",
+                ContactMethod::Sms => "sms",
+                ContactMethod::Email => "email",
+                ContactMethod::Post => "post",
+            }
+            .to_string(),
+            emergency_contact_name: owner.emergency_contact_name.unwrap_or_default(),
+            emergency_contact_phone: owner.emergency_contact_phone.unwrap_or_default(),
+            id: owner.id.to_string(),
+        }
+    }
+
+    fn preferred_contact(&self) -> Option<ContactMethod> {
+        match self.preferred_contact.as_str() {
+            "phone" => Some(ContactMethod::Phone),
+            "sms" => Some(ContactMethod::Sms),
+            "email" => Some(ContactMethod::Email),
+            "post" => Some(ContactMethod::Post),
+            _ => None,
+        }
+    }
+
+    /// 전화번호는 줄마다 phone_{줄 번호}_{칸 이름} key로 오류를 담음
+    fn validate_with(&self, default_country_code: &str) -> ValidationErrors {
+        let mut errors = self.validate().err().unwrap_or_default();
+        let mut add = |field: String, error: ValidationError| {
+            if let ValidationErrorsKind::Field(field_errors) = errors
+                .errors_mut()
+                .entry(Cow::Owned(field))
+                .or_insert_with(|| ValidationErrorsKind::Field(Vec::new()))
+            {
+                field_errors.push(error);
+            }
+        };
+
+        if self.phones.is_empty() {
+            add(
+                "phones".to_string(),
+                create_validation_error("length", "required"),
+            );
+        }
+        for (i, phone) in self.phones.iter().enumerate() {
+            if phone.kind().is_none() {
+                add(
+                    format!("phone_{i}_kind"),
+                    create_validation_error("unknown_kind", "notFound"),
+                );
+            }
+            if normalize_phone(&phone.number, default_country_code).is_none() {
+                add(
+                    format!("phone_{i}_number"),
+                    create_validation_error("phone", "typeMismatch.phone"),
+                );
+            }
+        }
+
+        // 연락 방법에 필요한 연락처가 있는지 확인함. 주소는 항상 필수임
+        let has_contact = match self.preferred_contact() {
+            None => {
+                add(
+                    "preferred_contact".to_string(),
+                    create_validation_error("unknown_contact_method", "notFound"),
+                );
+                true
+            }
+            Some(ContactMethod::Phone) => !self.phones.is_empty(),
+            Some(ContactMethod::Sms) => self
+                .phones
+                .iter()
+                .any(|phone| phone.kind() == Some(PhoneKind::Mobile)),
+            Some(ContactMethod::Email) => !self.email.is_empty(),
+            Some(ContactMethod::Post) => true,
+        };
+        if !has_contact {
+            add(
+                "preferred_contact".to_string(),
+                create_validation_error("contact_unavailable", "contactUnavailable"),
+            );
+        }
+
+        let name = self.emergency_contact_name.trim();
+        let phone = self.emergency_contact_phone.trim();
+        if !phone.is_empty() && normalize_phone(phone, default_country_code).is_none() {
+            add(
+                "emergency_contact_phone".to_string(),
+                create_validation_error("phone", "typeMismatch.phone"),
+            );
+        }
+        if name.is_empty() && !phone.is_empty() {
+            add(
+                "emergency_contact_name".to_string(),
+                create_validation_error("length", "required"),
+            );
+        }
+        if !name.is_empty() && phone.is_empty() {
+            add(
+                "emergency_contact_phone".to_string(),
+                create_validation_error("length", "required"),
+            );
+        }
+
+        errors
+    }
+
+    /// form data 검증 후에만 호출하므로 모든 전화번호와 연락 방법을 해석할 수 있음
+    fn to_contact_details(&self, default_country_code: &str) -> ContactDetails {
+        let non_empty = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+
+        ContactDetails {
+            email: non_empty(&self.email),
+            phones: self
+                .phones
+                .iter()
+                .map(|phone| {
+                    (
+                        phone.kind().unwrap(),
+                        normalize_phone(&phone.number, default_country_code).unwrap(),
+                    )
+                })
+                .collect(),
+            preferred_contact: self.preferred_contact().unwrap(),
+            emergency_contact_name: non_empty(&self.emergency_contact_name),
+            emergency_contact_phone: non_empty(&self.emergency_contact_phone)
+                .and_then(|phone| normalize_phone(&phone, default_country_code)),
+        }
+    }
+}
+
 #[post("/owners/new")]
 pub async fn process_creation_form(
     req: HttpRequest,
     app_state: web::Data<AppState>,
-    form: web::Form<CreateOrUpdateOwnerForm>,
+    form: web::Form<Vec<(String, String)>>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, i18n } = app_state.get_ref();
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state.get_ref();
 
-    let owner = form.into_inner();
+    let owner = CreateOrUpdateOwnerForm::from_pairs(form.into_inner());
+    let default_country_code = &config.contacts.default_country_code;
 
-    if let Err(errors) = owner.validate() {
+    let errors = owner.validate_with(default_country_code);
+    if !errors.is_empty() {
         return render_owner_form_with_errors(&req, tera, i18n, owner, errors);
     }
 
+    let contact = owner.to_contact_details(default_country_code);
     let new_owner = OwnerService::save_owner(
         conn,
         Some(owner.first_name),
         Some(owner.last_name),
         Some(owner.address),
         Some(owner.city),
-        Some(owner.telephone),
+        contact,
     )
     .await?;
 
@@ -118,7 +355,7 @@ fn render_owner_form_with_errors(
     tera: &tera::Tera,
     i18n: &I18n,
     owner_form: CreateOrUpdateOwnerForm,
-    errors: validator::ValidationErrors,
+    errors: ValidationErrors,
 ) -> Result<HttpResponse, AppError> {
     let errors = i18n.translate_errors(req, &errors);
 
@@ -131,11 +368,18 @@ fn render_owner_form_with_errors(
 }
 
 #[get("/owners/find")]
-pub async fn init_find_form(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+pub async fn init_find_form(
+    app_state: web::Data<AppState>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
     let AppState { tera, .. } = app_state.get_ref();
 
+    let (success_message, error_message) = extract_flash_messages(&messages);
+
     let mut ctx = Context::new();
     ctx.insert("current_menu", "owners");
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
 
     render(tera, "owner/find-owners.html", ctx)
 }
@@ -153,14 +397,13 @@ pub async fn process_find_form(
     app_state: web::Data<AppState>,
     query: web::Query<FindOwnerRequestQueryParams>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
     let query = query.into_inner();
-    let (last_name, cur_page, size) = (
-        query.last_name.unwrap_or("".to_string()),
-        query.page.unwrap_or(1),
-        query.size.unwrap_or(5),
-    );
+    let (cur_page, size) = page_and_size(query.page, query.size, &config.pagination)?;
+    let last_name = query.last_name.unwrap_or("".to_string());
 
     let mut ctx = Context::new();
     ctx.insert("current_menu", "owners");
@@ -183,7 +426,7 @@ pub async fn process_find_form(
         return Ok(redirect(format!("/owners/{}", owners_with_pet_names[0].id)));
     }
 
-    let page = Page::new(cur_page, owner_total_count);
+    let page = Page::new(cur_page, size, owner_total_count);
     let mut ctx = Context::new();
     ctx.insert("owners", &owners_with_pet_names);
     ctx.insert("last_name", &last_name);
@@ -201,12 +444,15 @@ pub async fn process_find_form(
 #[get(r"/owners/{owner_id:\d+}/edit")]
 pub async fn init_update_owner_form(
     app_state: web::Data<AppState>,
-    path: web::Path<u32>,
+    path: web::Path<i32>,
 ) -> Result<HttpResponse, AppError> {
     let AppState { conn, tera, .. } = app_state.get_ref();
 
     let owner_id = path.into_inner();
-    let owner = OwnerService::fetch_owner_by_id(conn, owner_id).await?;
+    let owner = CreateOrUpdateOwnerForm::from_model(
+        OwnerService::fetch_owner_by_id(conn, owner_id).await?,
+        OwnerService::fetch_phones_by_owner(conn, owner_id).await?,
+    );
 
     let mut ctx = Context::new();
     ctx.insert("owner", &owner);
@@ -219,19 +465,28 @@ pub async fn init_update_owner_form(
 pub async fn process_update_owner_form(
     req: HttpRequest,
     app_state: web::Data<AppState>,
-    path: web::Path<u32>,
-    form: web::Form<CreateOrUpdateOwnerForm>,
+    path: web::Path<i32>,
+    form: web::Form<Vec<(String, String)>>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, i18n } = app_state.get_ref();
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state.get_ref();
 
     let owner_id = path.into_inner();
-    let owner = form.into_inner();
+    let owner = CreateOrUpdateOwnerForm::from_pairs(form.into_inner());
+    let default_country_code = &config.contacts.default_country_code;
 
-    if let Err(errors) = owner.validate() {
+    let errors = owner.validate_with(default_country_code);
+    if !errors.is_empty() {
         return render_owner_form_with_errors(&req, tera, i18n, owner, errors);
     }
 
-    let body_owner_id = owner.id.parse::<u32>().unwrap();
+    // id를 보내지 않으면 빈 문자열이므로 path의 id와 다른 것으로 처리함
+    let body_owner_id = owner.id.parse::<i32>().unwrap_or_default();
     if body_owner_id != owner_id {
         return Err(AppError::ResourceIdMismatch {
             resource: "owner".to_string(),
@@ -240,6 +495,7 @@ pub async fn process_update_owner_form(
         });
     }
 
+    let contact = owner.to_contact_details(default_country_code);
     OwnerService::update_owner(
         conn,
         owner_id,
@@ -247,7 +503,7 @@ pub async fn process_update_owner_form(
         Some(owner.last_name),
         Some(owner.address),
         Some(owner.city),
-        Some(owner.telephone),
+        contact,
     )
     .await?;
 
@@ -255,3 +511,49 @@ pub async fn process_update_owner_form(
 
     Ok(redirect(format!("/owners/{owner_id}")))
 }
+
+#[get(r"/owners/{owner_id:\d+}/delete")]
+pub async fn init_delete_owner_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let owner_id = path.into_inner();
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("current_menu", "owners");
+
+    render(tera, "owner/delete-owner.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/delete")]
+pub async fn process_delete_owner_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let owner_id = path.into_inner();
+
+    match OwnerService::delete_owner(conn, owner_id).await {
+        Ok(()) => {
+            FlashMessage::info("Owner has been deleted").send();
+
+            Ok(redirect("/owners/find".to_string()))
+        }
+        Err(AppError::ResourceInUse { dependents, .. }) => {
+            FlashMessage::error(format!(
+                "Owner still has {dependents} and cannot be deleted"
+            ))
+            .send();
+
+            Ok(redirect(format!("/owners/{owner_id}")))
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -1,15 +1,28 @@
 use crate::{
+    config::app::PhotoConfig,
     domain::owner::owners,
-    model::app_error::AppError,
+    model::{
+        app_error::AppError,
+        page::Page,
+        photo::{photo_content_type, process_photo, ProcessedPhoto, PHOTO_CONTENT_TYPES},
+    },
     service::{
         owner_service::{OwnerService, PetWithTypeAndVisits},
-        pet_service::PetService,
+        pet_service::{PetService, PhotoSize},
+        pet_type_service::PetTypeService,
+    },
+    web::{
+        extract_flash_messages, page_and_size, redirect, render, validator::create_validation_error,
     },
-    web::{redirect, render, validator::create_validation_error},
     AppState,
 };
-use actix_web::{get, post, web, HttpRequest, HttpResponse};
-use actix_web_flash_messages::FlashMessage;
+use actix_multipart::form::{bytes::Bytes, text::Text, MultipartForm};
+use actix_web::{
+    get,
+    http::header::{self, CacheControl, CacheDirective, ETag, EntityTag, IfNoneMatch},
+    post, web, HttpMessage, HttpRequest, HttpResponse,
+};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
 use chrono::NaiveDate;
 use sea_orm::DbConn;
 use serde::{Deserialize, Serialize};
@@ -17,14 +30,16 @@ use tera::Context;
 use tokio::try_join;
 use validator::{Validate, ValidationErrors};
 
-use super::validator::{validate_not_blank, validate_pet_type, validate_today_or_past_date};
+use super::validator::{validate_not_blank, validate_today_or_past_date};
 
 #[get(r"/owners/{owner_id:\d+}/pets/new")]
 pub async fn init_creation_form(
     app_state: web::Data<AppState>,
-    path: web::Path<u32>,
+    path: web::Path<i32>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
     let owner_id = path.into_inner();
 
@@ -35,17 +50,18 @@ pub async fn init_creation_form(
     ctx.insert("owner", &owner);
     ctx.insert("pet_types", &pet_type_names);
     ctx.insert("is_new", &true);
+    ctx.insert("photo_max_bytes", &config.photos.max_bytes);
 
     render(tera, "pet/create-or-update-pet-form.html", ctx)
 }
 
 async fn get_owner_and_pet_types(
     conn: &DbConn,
-    owner_id: u32,
+    owner_id: i32,
 ) -> Result<(owners::Model, Vec<String>), AppError> {
     let (owner, pet_types) = try_join!(
         OwnerService::fetch_owner_by_id(conn, owner_id),
-        PetService::fetch_all_pet_types(conn)
+        PetTypeService::fetch_all_pet_types(conn)
     )?;
 
     let pet_type_names: Vec<String> = pet_types.into_iter().filter_map(|t| t.name).collect();
@@ -59,46 +75,70 @@ struct CreateOrUpdatePetForm {
     pet_name: String,
     #[validate(custom(function = validate_today_or_past_date))]
     birth_date: String,
-    #[validate(custom(function = validate_pet_type))]
+    #[validate(custom(function = validate_not_blank))]
     pet_type: String,
 }
 
+/// 사진 파일을 함께 받으므로 pet form은 multipart로 전송함
+#[derive(MultipartForm)]
+struct PetMultipartForm {
+    pet_name: Text<String>,
+    birth_date: Text<String>,
+    pet_type: Text<String>,
+    photo: Option<Bytes>,
+    remove_photo: Option<Text<String>>,
+}
+
+impl PetMultipartForm {
+    /// 파일을 고르지 않아도 browser는 빈 file part를 보내므로 내용이 없으면 사진을 올리지 않은 것으로 봄
+    fn into_parts(self) -> (CreateOrUpdatePetForm, Option<Bytes>, bool) {
+        let pet_form = CreateOrUpdatePetForm {
+            pet_name: self.pet_name.into_inner(),
+            birth_date: self.birth_date.into_inner(),
+            pet_type: self.pet_type.into_inner(),
+        };
+        let photo = self.photo.filter(|photo| !photo.data.is_empty());
+
+        (pet_form, photo, self.remove_photo.is_some())
+    }
+}
+
 #[post(r"/owners/{owner_id:\d+}/pets/new")]
 pub async fn process_creation_form(
     req: HttpRequest,
     app_state: web::Data<AppState>,
-    path: web::Path<u32>,
-    form: web::Form<CreateOrUpdatePetForm>,
+    path: web::Path<i32>,
+    MultipartForm(form): MultipartForm<PetMultipartForm>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, .. } = app_state.get_ref();
+    let AppState {
+        conn,
+        config,
+        storage,
+        ..
+    } = app_state.get_ref();
 
     let owner_id = path.into_inner();
-    let create_pet_form = form.into_inner();
+    let (create_pet_form, photo, _) = form.into_parts();
 
-    let errors = validate_pet_form(conn, owner_id, None, &create_pet_form).await?;
-    if !errors.is_empty() {
+    let (mut errors, pet_type_id) =
+        validate_pet_form(conn, owner_id, None, &create_pet_form).await?;
+    let photo = validate_photo(photo, &config.photos, &mut errors).await?;
+    let Some(pet_type_id) = pet_type_id.filter(|_| errors.is_empty()) else {
         return render_pet_form_with_errors(
             &req,
             app_state,
             owner_id,
+            None,
             create_pet_form,
             errors,
-            true,
         )
         .await;
-    }
-
-    let pet_type_id = PetService::fetch_all_pet_types(conn)
-        .await?
-        .iter()
-        .find(|t| t.name == Some(create_pet_form.pet_type.clone()))
-        .map(|t| t.id)
-        .unwrap(); // pet마다 pet type이 반드시 존재하므로 Some임
+    };
 
     // form data 검증 시 확인하였으므로 반드시 Some임
     let birth_date = NaiveDate::parse_from_str(&create_pet_form.birth_date, "%Y-%m-%d").unwrap();
 
-    PetService::save_pet(
+    let new_pet = PetService::save_pet(
         conn,
         Some(create_pet_form.pet_name),
         Some(birth_date),
@@ -106,68 +146,137 @@ pub async fn process_creation_form(
         Some(owner_id),
     )
     .await?;
+    if let Some(photo) = photo {
+        PetService::replace_photo(conn, storage.as_ref(), owner_id, new_pet.id, photo).await?;
+    }
 
     FlashMessage::info("New Pet has been Added").send();
 
     Ok(redirect(format!("/owners/{owner_id}")))
 }
 
+/// form data를 검증하고, form에서 고른 pet type이 `types` table에 있으면 그 id를 함께 반환함
 async fn validate_pet_form(
     conn: &DbConn,
-    owner_id: u32,
-    pet_id: Option<u32>,
+    owner_id: i32,
+    pet_id: Option<i32>,
     pet_form: &CreateOrUpdatePetForm,
-) -> Result<ValidationErrors, AppError> {
-    let errors = match pet_form.validate() {
-        Ok(_) => ValidationErrors::new(),
-        Err(mut errors) => {
-            let owner_with_pets =
-                OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(
-                    conn, owner_id,
-                )
-                .await?;
-
-            let pet_name_lowercase = pet_form.pet_name.to_lowercase();
-            let is_duplicate = owner_with_pets
-                .pets_with_type
-                .into_iter()
-                .filter(|p| {
-                    if let Some(id) = pet_id {
-                        p.pet_id != id
-                    } else {
-                        true
-                    }
-                })
-                .filter_map(|p| p.pet_name)
-                .any(|name| name.to_lowercase() == pet_name_lowercase);
-
-            if is_duplicate {
-                errors.add(
-                    "pet_name",
-                    create_validation_error("duplicate", "duplicate"),
-                );
+) -> Result<(ValidationErrors, Option<i32>), AppError> {
+    let mut errors = pet_form.validate().err().unwrap_or_default();
+
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+
+    let pet_name_lowercase = pet_form.pet_name.to_lowercase();
+    let is_duplicate = owner_with_pets
+        .pets_with_type
+        .into_iter()
+        .filter(|p| {
+            if let Some(id) = pet_id {
+                p.pet_id != id
+            } else {
+                true
             }
+        })
+        .filter_map(|p| p.pet_name)
+        .any(|name| name.to_lowercase() == pet_name_lowercase);
+
+    if is_duplicate {
+        errors.add(
+            "pet_name",
+            create_validation_error("duplicate", "duplicate"),
+        );
+    }
 
-            errors
-        }
+    let pet_type_id = PetTypeService::fetch_pet_type_id_by_name(conn, &pet_form.pet_type).await?;
+    if pet_type_id.is_none() && !errors.field_errors().contains_key("pet_type") {
+        errors.add(
+            "pet_type",
+            create_validation_error("unknown_pet_type", "notFound"),
+        );
+    }
+
+    Ok((errors, pet_type_id))
+}
+
+/// 사진 크기와 형식을 확인하고 저장할 크기로 줄임. 받을 수 없는 사진이면 errors에 추가하고 None을 반환함
+async fn validate_photo(
+    photo: Option<Bytes>,
+    config: &PhotoConfig,
+    errors: &mut ValidationErrors,
+) -> Result<Option<ProcessedPhoto>, AppError> {
+    let Some(photo) = photo else {
+        return Ok(None);
     };
 
-    Ok(errors)
+    if photo.data.len() > config.max_bytes {
+        errors.add(
+            "photo",
+            create_validation_error("too_large", "fileTooLarge"),
+        );
+        return Ok(None);
+    }
+    // 요청의 content type을 먼저 보고, 실제 파일 형식은 process_photo에서 내용으로 다시 확인함
+    let content_type = photo
+        .content_type
+        .as_ref()
+        .map(|mime| mime.essence_str())
+        .unwrap_or_default();
+    if !PHOTO_CONTENT_TYPES.contains(&content_type) {
+        errors.add(
+            "photo",
+            create_validation_error("unsupported_type", "typeMismatch.photo"),
+        );
+        return Ok(None);
+    }
+
+    // decode와 resize는 오래 걸릴 수 있으므로 worker thread를 막지 않도록 따로 실행함
+    let (display_size, thumbnail_size) = (config.display_size, config.thumbnail_size);
+    let processed =
+        web::block(move || process_photo(&photo.data, display_size, thumbnail_size)).await?;
+    if processed.is_err() {
+        errors.add(
+            "photo",
+            create_validation_error("invalid_image", "typeMismatch.photo"),
+        );
+    }
+
+    Ok(processed.ok())
+}
+
+fn photo_url(owner_id: i32, pet_id: i32, has_photo: bool) -> Option<String> {
+    has_photo.then(|| format!("/owners/{owner_id}/pets/{pet_id}/photo"))
 }
 
+/// pet_id가 None이면 새 pet form으로 보여줌
 async fn render_pet_form_with_errors(
     req: &HttpRequest,
     app_state: web::Data<AppState>,
-    owner_id: u32,
+    owner_id: i32,
+    pet_id: Option<i32>,
     pet_form: CreateOrUpdatePetForm,
     errors: validator::ValidationErrors,
-    is_new: bool,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, i18n } = app_state.get_ref();
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state.get_ref();
 
     let translated_errors = i18n.translate_errors(req, &errors);
 
     let (owner, pet_type_names) = get_owner_and_pet_types(conn, owner_id).await?;
+    // 올린 사진은 다시 골라야 하므로 이미 저장된 사진만 보여줌
+    let photo_url = match pet_id {
+        Some(pet_id) => {
+            let pet = PetService::fetch_pet(conn, owner_id, pet_id).await?;
+            photo_url(owner_id, pet_id, pet.photo_key.is_some())
+        }
+        None => None,
+    };
 
     let mut ctx = Context::new();
     ctx.insert("current_menu", "owners");
@@ -175,15 +284,17 @@ async fn render_pet_form_with_errors(
     ctx.insert("pet", &pet_form);
     ctx.insert("pet_types", &pet_type_names);
     ctx.insert("errors", &translated_errors);
-    ctx.insert("is_new", &is_new);
+    ctx.insert("is_new", &pet_id.is_none());
+    ctx.insert("photo_url", &photo_url);
+    ctx.insert("photo_max_bytes", &config.photos.max_bytes);
 
     render(tera, "pet/create-or-update-pet-form.html", ctx)
 }
 
 #[derive(Deserialize)]
 struct OwnerWithPetPathParams {
-    owner_id: u32,
-    pet_id: u32,
+    owner_id: i32,
+    pet_id: i32,
 }
 
 #[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/edit")]
@@ -191,19 +302,22 @@ pub async fn init_update_form(
     app_state: web::Data<AppState>,
     path: web::Path<OwnerWithPetPathParams>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
     let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
 
     let (owner_with_pets, pet_types) = try_join!(
         OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id),
-        PetService::fetch_all_pet_types(conn)
+        PetTypeService::fetch_all_pet_types(conn)
     )?;
 
     let PetWithTypeAndVisits {
         pet_name,
         birth_date,
         pet_type,
+        has_photo,
         ..
     } = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
     // pet name, birth_date, pet type name은 form data를 검증하기 때문에 반드시 존재하므로 Some임
@@ -219,13 +333,15 @@ pub async fn init_update_form(
     ctx.insert("owner", &owner_with_pets);
     ctx.insert("pet", &pet_form);
     ctx.insert("pet_types", &pet_type_names);
+    ctx.insert("photo_url", &photo_url(owner_id, pet_id, *has_photo));
+    ctx.insert("photo_max_bytes", &config.photos.max_bytes);
 
     render(tera, "pet/create-or-update-pet-form.html", ctx)
 }
 
 fn find_pet_by_id(
     pets: &[PetWithTypeAndVisits],
-    pet_id: u32,
+    pet_id: i32,
 ) -> Result<&PetWithTypeAndVisits, AppError> {
     pets.iter()
         .find(|p| p.pet_id == pet_id)
@@ -240,33 +356,33 @@ pub async fn process_update_form(
     req: HttpRequest,
     app_state: web::Data<AppState>,
     path: web::Path<OwnerWithPetPathParams>,
-    form: web::Form<CreateOrUpdatePetForm>,
+    MultipartForm(form): MultipartForm<PetMultipartForm>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, .. } = app_state.get_ref();
+    let AppState {
+        conn,
+        config,
+        storage,
+        ..
+    } = app_state.get_ref();
 
     let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
 
-    let update_pet_form = form.into_inner();
+    let (update_pet_form, photo, remove_photo) = form.into_parts();
 
-    let errors = validate_pet_form(conn, owner_id, Some(pet_id), &update_pet_form).await?;
-    if !errors.is_empty() {
+    let (mut errors, pet_type_id) =
+        validate_pet_form(conn, owner_id, Some(pet_id), &update_pet_form).await?;
+    let photo = validate_photo(photo, &config.photos, &mut errors).await?;
+    let Some(pet_type_id) = pet_type_id.filter(|_| errors.is_empty()) else {
         return render_pet_form_with_errors(
             &req,
             app_state,
             owner_id,
+            Some(pet_id),
             update_pet_form,
             errors,
-            false,
         )
         .await;
-    }
-
-    let pet_type_id = PetService::fetch_all_pet_types(conn)
-        .await?
-        .iter()
-        .find(|t| t.name == Some(update_pet_form.pet_type.clone()))
-        .map(|t| t.id)
-        .unwrap(); // pet마다 pet type이 반드시 존재하므로 Some임
+    };
 
     // form data 검증 시 확인하였으므로 반드시 Some임
     let birth_date = NaiveDate::parse_from_str(&update_pet_form.birth_date, "%Y-%m-%d").unwrap();
@@ -279,8 +395,277 @@ pub async fn process_update_form(
         pet_type_id,
     )
     .await?;
+    // 새 사진을 올리면서 삭제도 선택했다면 새 사진으로 바꿈
+    if let Some(photo) = photo {
+        PetService::replace_photo(conn, storage.as_ref(), owner_id, pet_id, photo).await?;
+    } else if remove_photo {
+        PetService::remove_photo(conn, storage.as_ref(), owner_id, pet_id).await?;
+    }
 
     FlashMessage::info("Pet details has been edited").send();
 
     Ok(redirect(format!("/owners/{owner_id}")))
 }
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/photo")]
+pub async fn show_photo(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+) -> Result<HttpResponse, AppError> {
+    photo_response(
+        &req,
+        app_state.get_ref(),
+        path.into_inner(),
+        PhotoSize::Full,
+    )
+    .await
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/photo/thumbnail")]
+pub async fn show_photo_thumbnail(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+) -> Result<HttpResponse, AppError> {
+    photo_response(
+        &req,
+        app_state.get_ref(),
+        path.into_inner(),
+        PhotoSize::Thumbnail,
+    )
+    .await
+}
+
+/// 저장소를 직접 공개하지 않고, pet이 owner에게 속하는지 확인한 뒤 현재 사진만 내려줌.
+/// 사진을 바꾸면 key도 바뀌므로 key를 ETag로 써서 바뀌지 않은 사진은 304로 응답함
+async fn photo_response(
+    req: &HttpRequest,
+    app_state: &AppState,
+    params: OwnerWithPetPathParams,
+    size: PhotoSize,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, storage, .. } = app_state;
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = params;
+
+    let key = PetService::fetch_photo_key(conn, owner_id, pet_id, size).await?;
+    let etag = EntityTag::new_strong(key.clone());
+
+    let is_unchanged = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => false,
+    };
+    if is_unchanged {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(ETag(etag))
+            .finish());
+    }
+
+    let content = PetService::read_photo(storage.as_ref(), pet_id, &key).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(photo_content_type(&key))
+        .insert_header(ETag(etag))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Private,
+            CacheDirective::NoCache,
+        ]))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(content))
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/delete")]
+pub async fn init_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
+
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+    let pet = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+
+    let mut ctx = Context::new();
+    ctx.insert("current_menu", "owners");
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", &pet);
+
+    render(tera, "pet/delete-pet.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/delete")]
+pub async fn process_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, storage, .. } = app_state.get_ref();
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
+
+    match PetService::delete_pet(conn, storage.as_ref(), owner_id, pet_id).await {
+        Ok(deleted_visits) => FlashMessage::info(format!(
+            "Pet has been deleted along with {deleted_visits} visit(s)"
+        ))
+        .send(),
+        Err(AppError::ResourceInUse { dependents, .. }) => FlashMessage::error(format!(
+            "Pet has {dependents} on invoices and cannot be deleted"
+        ))
+        .send(),
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
+
+#[derive(Deserialize)]
+struct TransferSearchQuery {
+    last_name: Option<String>,
+    page: Option<u64>,
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/transfer")]
+pub async fn init_transfer_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+    query: web::Query<TransferSearchQuery>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
+
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+    let pet = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+
+    let (_, error_message) = extract_flash_messages(&messages);
+
+    let mut ctx = Context::new();
+    ctx.insert("current_menu", "owners");
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", &pet);
+    ctx.insert("error_message", &error_message);
+
+    // 검색하기 전에는 후보 owner를 보여주지 않음
+    if let Some(last_name) = query.last_name.clone() {
+        let (cur_page, size) = page_and_size(query.page, None, &config.pagination)?;
+
+        let owner_total_count =
+            OwnerService::fetch_owner_count_by_last_name_prefix(conn, &last_name).await?;
+        let candidates = if owner_total_count > 0 {
+            OwnerService::fetch_owners_with_pet_names(conn, &last_name, cur_page, size).await?
+        } else {
+            vec![]
+        };
+
+        let page = Page::new(cur_page, size, owner_total_count);
+        ctx.insert("candidates", &candidates);
+        ctx.insert("last_name", &last_name);
+        ctx.insert("page", &cur_page);
+        ctx.insert("total_pages", &page.total_pages());
+        ctx.insert("has_previous", &page.has_previous());
+        ctx.insert("has_next", &page.has_next());
+        ctx.insert("page_range", page.page_range());
+        ctx.insert("query_params", &vec![("last_name", last_name)]);
+    }
+
+    render(tera, "pet/transfer-pet.html", ctx)
+}
+
+#[derive(Deserialize)]
+struct PetTransferPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    new_owner_id: i32,
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/transfer/{new_owner_id:\d+}")]
+pub async fn init_confirm_transfer_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<PetTransferPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let PetTransferPathParams {
+        owner_id,
+        pet_id,
+        new_owner_id,
+    } = path.into_inner();
+
+    if new_owner_id == owner_id {
+        FlashMessage::error("The pet already belongs to this owner").send();
+
+        return Ok(redirect(format!(
+            "/owners/{owner_id}/pets/{pet_id}/transfer"
+        )));
+    }
+
+    let (owner_with_pets, new_owner) = try_join!(
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id),
+        OwnerService::fetch_owner_by_id(conn, new_owner_id)
+    )?;
+    let pet = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+
+    let mut ctx = Context::new();
+    ctx.insert("current_menu", "owners");
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", &pet);
+    ctx.insert("new_owner", &new_owner);
+
+    render(tera, "pet/confirm-transfer-pet.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/transfer/{new_owner_id:\d+}")]
+pub async fn process_transfer_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<PetTransferPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let PetTransferPathParams {
+        owner_id,
+        pet_id,
+        new_owner_id,
+    } = path.into_inner();
+
+    match PetService::transfer_pet(conn, owner_id, pet_id, new_owner_id).await {
+        Ok(_) => {
+            FlashMessage::info("Pet has been transferred with its visits").send();
+
+            Ok(redirect(format!("/owners/{new_owner_id}")))
+        }
+        Err(AppError::DuplicateResource { .. }) => {
+            FlashMessage::error("The new owner already has a pet with this name").send();
+
+            Ok(redirect(format!(
+                "/owners/{owner_id}/pets/{pet_id}/transfer"
+            )))
+        }
+        Err(AppError::InvalidResourceState { .. }) => {
+            FlashMessage::error("The pet already belongs to this owner").send();
+
+            Ok(redirect(format!(
+                "/owners/{owner_id}/pets/{pet_id}/transfer"
+            )))
+        }
+        Err(AppError::ResourceInUse { dependents, .. }) => {
+            FlashMessage::error(format!(
+                "Pet has {dependents}. Invoice or delete them before transferring"
+            ))
+            .send();
+
+            Ok(redirect(format!(
+                "/owners/{owner_id}/pets/{pet_id}/transfer"
+            )))
+        }
+        Err(e) => Err(e),
+    }
+}
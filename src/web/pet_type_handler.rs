@@ -0,0 +1,215 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use serde::{Deserialize, Serialize};
+use tera::Context;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    model::app_error::AppError,
+    service::pet_type_service::PetTypeService,
+    web::{
+        extract_flash_messages, redirect, render,
+        validator::{create_validation_error, validate_not_blank},
+    },
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Validate)]
+struct CreateOrUpdatePetTypeForm {
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 80, message = "80자를 넘을 수 없습니다")
+    )]
+    name: String,
+}
+
+#[get("/pet-types")]
+pub async fn show_pet_type_list(
+    app_state: web::Data<AppState>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let pet_types = PetTypeService::fetch_pet_types_with_pet_count(conn).await?;
+
+    let (success_message, error_message) = extract_flash_messages(&messages);
+
+    let mut ctx = Context::new();
+    ctx.insert("pet_types", &pet_types);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
+    ctx.insert("current_menu", "pet-types");
+
+    render(tera, "pet/pet-type-list.html", ctx)
+}
+
+#[get("/pet-types/new")]
+pub async fn init_creation_form(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let AppState { tera, .. } = app_state.get_ref();
+
+    let mut ctx = Context::new();
+    ctx.insert("current_menu", "pet-types");
+
+    render(tera, "pet/create-or-update-pet-type-form.html", ctx)
+}
+
+fn render_pet_type_form_with_errors(
+    req: &HttpRequest,
+    app_state: &AppState,
+    pet_type_id: Option<i32>,
+    pet_type_form: &CreateOrUpdatePetTypeForm,
+    errors: &ValidationErrors,
+) -> Result<HttpResponse, AppError> {
+    let AppState { tera, i18n, .. } = app_state;
+
+    let mut ctx = Context::new();
+    ctx.insert("pet_type", pet_type_form);
+    ctx.insert("pet_type_id", &pet_type_id);
+    ctx.insert("errors", &i18n.translate_errors(req, errors));
+    ctx.insert("current_menu", "pet-types");
+
+    render(tera, "pet/create-or-update-pet-type-form.html", ctx)
+}
+
+fn duplicate_name_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("name", create_validation_error("duplicate", "duplicate"));
+
+    errors
+}
+
+#[post("/pet-types/new")]
+pub async fn process_creation_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    form: web::Form<CreateOrUpdatePetTypeForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let pet_type_form = form.into_inner();
+
+    if let Err(errors) = pet_type_form.validate() {
+        return render_pet_type_form_with_errors(&req, &app_state, None, &pet_type_form, &errors);
+    }
+
+    match PetTypeService::save_pet_type(conn, &pet_type_form.name).await {
+        Ok(_) => {}
+        Err(AppError::DuplicateResource { .. }) => {
+            return render_pet_type_form_with_errors(
+                &req,
+                &app_state,
+                None,
+                &pet_type_form,
+                &duplicate_name_errors(),
+            );
+        }
+        Err(e) => return Err(e),
+    }
+
+    FlashMessage::info("New Pet Type Created").send();
+
+    Ok(redirect("/pet-types".to_string()))
+}
+
+#[get(r"/pet-types/{pet_type_id:\d+}/edit")]
+pub async fn init_update_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let pet_type_id = path.into_inner();
+    let pet_type = PetTypeService::fetch_pet_type_by_id(conn, pet_type_id).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("pet_type", &pet_type);
+    ctx.insert("pet_type_id", &pet_type_id);
+    ctx.insert("current_menu", "pet-types");
+
+    render(tera, "pet/create-or-update-pet-type-form.html", ctx)
+}
+
+#[post(r"/pet-types/{pet_type_id:\d+}/edit")]
+pub async fn process_update_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    form: web::Form<CreateOrUpdatePetTypeForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let pet_type_id = path.into_inner();
+    let pet_type_form = form.into_inner();
+
+    if let Err(errors) = pet_type_form.validate() {
+        return render_pet_type_form_with_errors(
+            &req,
+            &app_state,
+            Some(pet_type_id),
+            &pet_type_form,
+            &errors,
+        );
+    }
+
+    match PetTypeService::update_pet_type(conn, pet_type_id, &pet_type_form.name).await {
+        Ok(_) => {}
+        Err(AppError::DuplicateResource { .. }) => {
+            return render_pet_type_form_with_errors(
+                &req,
+                &app_state,
+                Some(pet_type_id),
+                &pet_type_form,
+                &duplicate_name_errors(),
+            );
+        }
+        Err(e) => return Err(e),
+    }
+
+    FlashMessage::info("Pet Type Values Updated").send();
+
+    Ok(redirect("/pet-types".to_string()))
+}
+
+#[get(r"/pet-types/{pet_type_id:\d+}/delete")]
+pub async fn init_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let pet_type_id = path.into_inner();
+    let pet_type = PetTypeService::fetch_pet_types_with_pet_count(conn)
+        .await?
+        .into_iter()
+        .find(|t| t.id == pet_type_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "pet type".to_string(),
+            id: pet_type_id,
+        })?;
+
+    let mut ctx = Context::new();
+    ctx.insert("pet_type", &pet_type);
+    ctx.insert("current_menu", "pet-types");
+
+    render(tera, "pet/delete-pet-type.html", ctx)
+}
+
+#[post(r"/pet-types/{pet_type_id:\d+}/delete")]
+pub async fn process_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let pet_type_id = path.into_inner();
+
+    match PetTypeService::delete_pet_type(conn, pet_type_id).await {
+        Ok(_) => FlashMessage::info("Pet type has been deleted").send(),
+        Err(AppError::ResourceInUse { .. }) => {
+            FlashMessage::error("Pets of this type still exist. Change their type first").send()
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect("/pet-types".to_string()))
+}
@@ -0,0 +1,321 @@
+use actix_web::{delete, get, http, post, put, web, HttpRequest, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
+use serde::{Deserialize, Serialize};
+use tera::Context;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    domain::veterinarian::specialty,
+    model::app_error::AppError,
+    service::specialty_service::{SpecialtyService, SpecialtyWithVetCount},
+    web::{
+        extract_flash_messages, redirect, render,
+        validator::{create_validation_error, validate_not_blank},
+    },
+    AppState,
+};
+
+#[derive(Serialize, Deserialize, Validate)]
+struct CreateOrUpdateSpecialtyForm {
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 80, message = "80자를 넘을 수 없습니다")
+    )]
+    name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpecialtyResponse {
+    id: i32,
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    vet_count: Option<i64>,
+}
+
+impl From<specialty::Model> for SpecialtyResponse {
+    fn from(value: specialty::Model) -> Self {
+        SpecialtyResponse {
+            id: value.id,
+            name: value.name,
+            vet_count: None,
+        }
+    }
+}
+
+impl From<SpecialtyWithVetCount> for SpecialtyResponse {
+    fn from(value: SpecialtyWithVetCount) -> Self {
+        SpecialtyResponse {
+            id: value.id,
+            name: value.name,
+            vet_count: Some(value.vet_count),
+        }
+    }
+}
+
+#[get("/specialties")]
+pub async fn show_specialty_list(
+    app_state: web::Data<AppState>,
+    messages: IncomingFlashMessages,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let specialties = SpecialtyService::fetch_specialties_with_vet_count(conn).await?;
+
+    let (success_message, error_message) = extract_flash_messages(&messages);
+
+    let mut ctx = Context::new();
+    ctx.insert("specialties", &specialties);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "specialty/specialty-list.html", ctx)
+}
+
+#[get("/specialties/new")]
+pub async fn init_creation_form(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let AppState { tera, .. } = app_state.get_ref();
+
+    let mut ctx = Context::new();
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "specialty/create-or-update-specialty-form.html", ctx)
+}
+
+fn render_specialty_form_with_errors(
+    req: &HttpRequest,
+    app_state: &AppState,
+    specialty_id: Option<i32>,
+    specialty_form: &CreateOrUpdateSpecialtyForm,
+    errors: &ValidationErrors,
+) -> Result<HttpResponse, AppError> {
+    let AppState { tera, i18n, .. } = app_state;
+
+    let mut ctx = Context::new();
+    ctx.insert("specialty", specialty_form);
+    ctx.insert("specialty_id", &specialty_id);
+    ctx.insert("errors", &i18n.translate_errors(req, errors));
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "specialty/create-or-update-specialty-form.html", ctx)
+}
+
+fn duplicate_name_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("name", create_validation_error("duplicate", "duplicate"));
+
+    errors
+}
+
+#[post("/specialties/new")]
+pub async fn process_creation_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    form: web::Form<CreateOrUpdateSpecialtyForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let specialty_form = form.into_inner();
+
+    if let Err(errors) = specialty_form.validate() {
+        return render_specialty_form_with_errors(&req, &app_state, None, &specialty_form, &errors);
+    }
+
+    match SpecialtyService::save_specialty(conn, &specialty_form.name).await {
+        Ok(_) => {}
+        Err(AppError::DuplicateResource { .. }) => {
+            return render_specialty_form_with_errors(
+                &req,
+                &app_state,
+                None,
+                &specialty_form,
+                &duplicate_name_errors(),
+            );
+        }
+        Err(e) => return Err(e),
+    }
+
+    FlashMessage::info("New Specialty Created").send();
+
+    Ok(redirect("/specialties".to_string()))
+}
+
+#[get(r"/specialties/{specialty_id:\d+}/edit")]
+pub async fn init_update_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let specialty_id = path.into_inner();
+    let specialty = SpecialtyService::fetch_specialty_by_id(conn, specialty_id).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("specialty", &specialty);
+    ctx.insert("specialty_id", &specialty_id);
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "specialty/create-or-update-specialty-form.html", ctx)
+}
+
+#[post(r"/specialties/{specialty_id:\d+}/edit")]
+pub async fn process_update_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    form: web::Form<CreateOrUpdateSpecialtyForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let specialty_id = path.into_inner();
+    let specialty_form = form.into_inner();
+
+    if let Err(errors) = specialty_form.validate() {
+        return render_specialty_form_with_errors(
+            &req,
+            &app_state,
+            Some(specialty_id),
+            &specialty_form,
+            &errors,
+        );
+    }
+
+    match SpecialtyService::update_specialty(conn, specialty_id, &specialty_form.name).await {
+        Ok(_) => {}
+        Err(AppError::DuplicateResource { .. }) => {
+            return render_specialty_form_with_errors(
+                &req,
+                &app_state,
+                Some(specialty_id),
+                &specialty_form,
+                &duplicate_name_errors(),
+            );
+        }
+        Err(e) => return Err(e),
+    }
+
+    FlashMessage::info("Specialty Values Updated").send();
+
+    Ok(redirect("/specialties".to_string()))
+}
+
+#[get(r"/specialties/{specialty_id:\d+}/delete")]
+pub async fn init_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let specialty_id = path.into_inner();
+    let specialty = SpecialtyService::fetch_specialties_with_vet_count(conn)
+        .await?
+        .into_iter()
+        .find(|s| s.id == specialty_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "specialty".to_string(),
+            id: specialty_id,
+        })?;
+
+    let mut ctx = Context::new();
+    ctx.insert("specialty", &specialty);
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "specialty/delete-specialty.html", ctx)
+}
+
+#[post(r"/specialties/{specialty_id:\d+}/delete")]
+pub async fn process_delete_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let specialty_id = path.into_inner();
+
+    match SpecialtyService::delete_specialty(conn, specialty_id).await {
+        Ok(_) => FlashMessage::info("Specialty has been deleted").send(),
+        Err(AppError::ResourceInUse { .. }) => FlashMessage::error(
+            "Specialty is still assigned to vets. Remove it from the vets first",
+        )
+        .send(),
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect("/specialties".to_string()))
+}
+
+#[get("/api/specialties")]
+pub async fn list_specialties(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let specialties: Vec<SpecialtyResponse> =
+        SpecialtyService::fetch_specialties_with_vet_count(conn)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+    Ok(HttpResponse::Ok().json(specialties))
+}
+
+#[get(r"/api/specialties/{specialty_id:\d+}")]
+pub async fn get_specialty(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let specialty = SpecialtyService::fetch_specialty_by_id(conn, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(SpecialtyResponse::from(specialty)))
+}
+
+#[post("/api/specialties")]
+pub async fn create_specialty(
+    app_state: web::Data<AppState>,
+    body: web::Json<CreateOrUpdateSpecialtyForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let request = body.into_inner();
+    request.validate()?;
+
+    let new_specialty = SpecialtyService::save_specialty(conn, &request.name).await?;
+
+    Ok(HttpResponse::Created()
+        .append_header((
+            http::header::LOCATION,
+            format!("/api/specialties/{}", new_specialty.id),
+        ))
+        .json(SpecialtyResponse::from(new_specialty)))
+}
+
+#[put(r"/api/specialties/{specialty_id:\d+}")]
+pub async fn update_specialty(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    body: web::Json<CreateOrUpdateSpecialtyForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let request = body.into_inner();
+    request.validate()?;
+
+    let updated_specialty =
+        SpecialtyService::update_specialty(conn, path.into_inner(), &request.name).await?;
+
+    Ok(HttpResponse::Ok().json(SpecialtyResponse::from(updated_specialty)))
+}
+
+#[delete(r"/api/specialties/{specialty_id:\d+}")]
+pub async fn delete_specialty(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    SpecialtyService::delete_specialty(conn, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,206 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web_flash_messages::FlashMessage;
+use chrono::NaiveDate;
+use sea_orm::DbConn;
+use serde::{Deserialize, Serialize};
+use tera::Context;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    model::app_error::AppError,
+    service::{
+        owner_service::OwnerService,
+        vaccination_service::{NewVaccination, VaccinationService},
+        vet_service::VetService,
+    },
+    web::{
+        redirect, render,
+        validator::{create_validation_error, validate_not_blank, validate_today_or_past_date},
+    },
+    AppState,
+};
+
+#[derive(Deserialize)]
+struct OwnerWithPetPathParams {
+    owner_id: i32,
+    pet_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Validate)]
+struct CreateVaccinationForm {
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 80, message = "80자를 넘을 수 없습니다")
+    )]
+    vaccine: String,
+    #[validate(custom(function = validate_today_or_past_date))]
+    given_on: String,
+    #[validate(length(max = 40, message = "40자를 넘을 수 없습니다"))]
+    lot_number: String,
+    /// 다른 병원에서 접종한 경우 비어 있음
+    vet_id: String,
+    /// 다음 접종이 필요 없으면 비어 있음
+    next_due_on: String,
+}
+
+impl CreateVaccinationForm {
+    /// form data 검증 후에만 호출하므로 날짜와 vet id는 해석할 수 있음
+    fn to_new_vaccination(&self) -> NewVaccination {
+        NewVaccination {
+            vaccine: self.vaccine.trim().to_string(),
+            given_on: NaiveDate::parse_from_str(&self.given_on, "%Y-%m-%d").unwrap(),
+            lot_number: Some(self.lot_number.trim().to_string()).filter(|l| !l.is_empty()),
+            vet_id: self.vet_id.parse().ok(),
+            next_due_on: NaiveDate::parse_from_str(&self.next_due_on, "%Y-%m-%d").ok(),
+        }
+    }
+}
+
+async fn validate_vaccination_form(
+    conn: &DbConn,
+    vaccination_form: &CreateVaccinationForm,
+) -> Result<ValidationErrors, AppError> {
+    let mut errors = vaccination_form.validate().err().unwrap_or_default();
+
+    if !vaccination_form.vet_id.is_empty() {
+        let is_active_vet = match vaccination_form.vet_id.parse::<i32>() {
+            Ok(vet_id) => VetService::fetch_active_vets(conn)
+                .await?
+                .iter()
+                .any(|v| v.id == vet_id),
+            Err(_) => false,
+        };
+
+        if !is_active_vet {
+            errors.add("vet_id", create_validation_error("unknown_vet", "notFound"));
+        }
+    }
+
+    if !vaccination_form.next_due_on.is_empty() {
+        match NaiveDate::parse_from_str(&vaccination_form.next_due_on, "%Y-%m-%d") {
+            Err(_) => errors.add(
+                "next_due_on",
+                create_validation_error("invalid_date", "typeMismatch.birthDate"),
+            ),
+            Ok(next_due_on) => {
+                let given_on = NaiveDate::parse_from_str(&vaccination_form.given_on, "%Y-%m-%d");
+                if given_on.is_ok_and(|given_on| next_due_on <= given_on) {
+                    errors.add(
+                        "next_due_on",
+                        create_validation_error("due_before_given", "dueBeforeGiven"),
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(errors)
+}
+
+async fn create_vaccination_form_context(
+    conn: &DbConn,
+    owner_id: i32,
+    pet_id: i32,
+) -> Result<Context, AppError> {
+    let owner_with_pets_and_types =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+
+    let pet = owner_with_pets_and_types
+        .pets_with_type
+        .iter()
+        .find(|p| p.pet_id == pet_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "pet".to_string(),
+            id: pet_id,
+        })?;
+    let vets = VetService::fetch_active_vets(conn).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets_and_types);
+    ctx.insert("pet", pet);
+    ctx.insert("vets", &vets);
+    ctx.insert("current_menu", "owners");
+
+    Ok(ctx)
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/vaccinations/new")]
+pub async fn init_new_vaccination_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
+
+    let ctx = create_vaccination_form_context(conn, owner_id, pet_id).await?;
+
+    render(tera, "pet/create-vaccination-form.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/vaccinations/new")]
+pub async fn process_new_vaccination_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<OwnerWithPetPathParams>,
+    form: web::Form<CreateVaccinationForm>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, i18n, ..
+    } = app_state.get_ref();
+
+    let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
+    let vaccination_form = form.into_inner();
+
+    let errors = validate_vaccination_form(conn, &vaccination_form).await?;
+    if !errors.is_empty() {
+        let mut ctx = create_vaccination_form_context(conn, owner_id, pet_id).await?;
+        ctx.insert("vaccination", &vaccination_form);
+        ctx.insert("errors", &i18n.translate_errors(&req, &errors));
+
+        return render(tera, "pet/create-vaccination-form.html", ctx);
+    }
+
+    VaccinationService::save_vaccination(
+        conn,
+        owner_id,
+        pet_id,
+        vaccination_form.to_new_vaccination(),
+    )
+    .await?;
+
+    FlashMessage::info("Vaccination has been recorded").send();
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
+
+#[derive(Deserialize)]
+struct DueReportParams {
+    days: Option<u32>,
+}
+
+/// 다음 접종일이 지났거나 `days`일(기본값은 설정의 due_soon_days) 안에 있는 접종 목록
+#[get("/vaccinations/due")]
+pub async fn show_due_vaccinations(
+    app_state: web::Data<AppState>,
+    params: web::Query<DueReportParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let days = params
+        .days
+        .unwrap_or(config.vaccinations.due_soon_days)
+        .min(365);
+
+    let due_vaccinations = VaccinationService::fetch_due_vaccinations(conn, days).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("due_vaccinations", &due_vaccinations);
+    ctx.insert("days", &days);
+    ctx.insert("current_menu", "vaccinations");
+
+    render(tera, "vaccination/due-report.html", ctx)
+}
@@ -1,9 +1,9 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
-use chrono::{Local, NaiveDate};
+use chrono::{Local, NaiveDate, NaiveTime};
 use validator::ValidationError;
 
-const VALID_PET_TYPES: [&str; 6] = ["bird", "cat", "dog", "hamster", "lizard", "snake"];
+use crate::model::money::Money;
 
 pub fn validate_not_blank(data: &str) -> Result<(), ValidationError> {
     if data.trim().is_empty() {
@@ -29,7 +29,7 @@ pub fn validate_today_or_past_date(date: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
-fn validate_date_format(date: &str) -> Result<(), ValidationError> {
+pub fn validate_date_format(date: &str) -> Result<(), ValidationError> {
     validate_not_blank(date)?;
 
     if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
@@ -58,19 +58,111 @@ pub fn validate_future_date(date: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
-pub fn validate_pet_type(data: &str) -> Result<(), ValidationError> {
-    validate_not_blank(data)?;
+pub fn validate_time(time: &str) -> Result<(), ValidationError> {
+    validate_not_blank(time)?;
 
-    if !VALID_PET_TYPES.contains(&data) {
+    if NaiveTime::parse_from_str(time, "%H:%M").is_err() {
+        return Err(create_validation_error("invalid_time", "typeMismatch.time"));
+    }
+
+    Ok(())
+}
+
+/// "12.34" 형식의 0 이상 금액
+pub fn validate_amount(amount: &str) -> Result<(), ValidationError> {
+    validate_not_blank(amount)?;
+
+    if Money::parse(amount).is_none() {
         return Err(create_validation_error(
-            "invalid_pet_type",
-            "존재하지 않는 pet type 입니다",
+            "invalid_amount",
+            "typeMismatch.amount",
         ));
     }
 
     Ok(())
 }
 
+/// 반복되는 form 줄 하나. prefix를 뗀 field 이름으로 값을 꺼냄
+#[derive(Debug, Default, PartialEq)]
+pub struct FormRow(HashMap<String, String>);
+
+impl FormRow {
+    /// 보내지 않은 field는 빈 문자열임
+    pub fn take(&mut self, field: &str) -> String {
+        self.0.remove(field).unwrap_or_default()
+    }
+}
+
+/// 전화번호나 처방처럼 같은 key를 여러 번 보내는 줄을 key-value 쌍에서 나눔.
+/// `prefix`로 시작하는 key는 줄의 field이며, `first_field`가 올 때마다 새 줄을 시작함.
+/// 첫 줄이 시작되기 전에 온 줄 field는 버리고, 나머지 key-value 쌍은 순서대로 돌려줌
+pub fn split_form_rows(
+    pairs: Vec<(String, String)>,
+    prefix: &str,
+    first_field: &str,
+) -> (Vec<(String, String)>, Vec<FormRow>) {
+    let mut fields = Vec::new();
+    let mut rows: Vec<FormRow> = Vec::new();
+    for (key, value) in pairs {
+        let Some(field) = key.strip_prefix(prefix) else {
+            fields.push((key, value));
+            continue;
+        };
+
+        if field == first_field {
+            rows.push(FormRow::default());
+        }
+        if let Some(row) = rows.last_mut() {
+            row.0.insert(field.to_string(), value);
+        }
+    }
+
+    (fields, rows)
+}
+
 pub fn create_validation_error(code: &'static str, message: &'static str) -> ValidationError {
     ValidationError::new(code).with_message(Cow::Borrowed(message))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_form_rows() {
+        let (fields, mut rows) = split_form_rows(
+            pairs(&[
+                ("rx_drug", "orphan"),
+                ("date", "2030-05-06"),
+                ("rx_kind", "treatment"),
+                ("rx_drug", "Scaling"),
+                ("description", "dental"),
+                ("rx_kind", "prescription"),
+                ("rx_drug", "Amoxicillin"),
+                ("rx_dose", "50mg"),
+                ("rx_kind", ""),
+            ]),
+            "rx_",
+            "kind",
+        );
+
+        assert_eq!(
+            fields,
+            pairs(&[("date", "2030-05-06"), ("description", "dental")])
+        );
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].take("kind"), "treatment");
+        assert_eq!(rows[0].take("drug"), "Scaling");
+        assert_eq!(rows[0].take("dose"), "");
+        assert_eq!(rows[1].take("drug"), "Amoxicillin");
+        assert_eq!(rows[1].take("dose"), "50mg");
+        assert_eq!(rows[2].take("drug"), "");
+    }
+}
@@ -1,11 +1,20 @@
-use actix_web::{get, web, HttpResponse};
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web_flash_messages::{FlashMessage, IncomingFlashMessages};
 use serde::{Deserialize, Serialize};
 use tera::Context;
+use validator::{Validate, ValidationErrors};
 
 use crate::{
+    domain::veterinarian::specialty,
     model::{app_error::AppError, page::Page},
-    service::vet_service::{self, VetService, VetWithSpecialties},
-    web::render,
+    service::{
+        specialty_service::SpecialtyService,
+        vet_service::{self, VetService, VetWithSpecialties},
+    },
+    web::{
+        extract_flash_messages, page_and_size, redirect, render,
+        validator::{create_validation_error, validate_not_blank},
+    },
     AppState,
 };
 
@@ -19,7 +28,7 @@ struct ShowResourcesVetListResponse {
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Vet {
-    id: u32,
+    id: i32,
     first_name: Option<String>,
     last_name: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -39,7 +48,7 @@ impl From<VetWithSpecialties> for Vet {
 
 #[derive(Serialize)]
 pub struct Specialty {
-    id: u32,
+    id: i32,
     name: Option<String>,
 }
 
@@ -73,34 +82,313 @@ pub async fn show_resources_vet_list(
 struct ShowVetListQuery {
     page: Option<u64>,
     size: Option<u64>,
+    retired: Option<bool>,
 }
 
 #[get("/vets.html")]
 pub async fn show_vet_list(
     app_state: web::Data<AppState>,
     query: web::Query<ShowVetListQuery>,
+    messages: IncomingFlashMessages,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
-    let (cur_page, size) = (query.page.unwrap_or(1), query.size.unwrap_or(5));
+    let (cur_page, size) = page_and_size(query.page, query.size, &config.pagination)?;
+    let include_retired = query.retired.unwrap_or(false);
 
-    let vet_total_count = VetService::fetch_all_vets_count(conn).await?;
+    let vet_total_count = VetService::fetch_all_vets_count(conn, include_retired).await?;
     let vet_list = if vet_total_count > 0 {
-        VetService::fetch_vets_with_specialties_paginated(conn, cur_page, size).await?
+        VetService::fetch_vets_with_specialties_paginated(conn, cur_page, size, include_retired)
+            .await?
     } else {
         vec![]
     };
 
-    let page = Page::new(cur_page, vet_total_count);
+    let (success_message, error_message) = extract_flash_messages(&messages);
+
+    let page = Page::new(cur_page, size, vet_total_count);
     let mut ctx = Context::new();
     ctx.insert("vets", &vet_list);
+    ctx.insert("include_retired", &include_retired);
     ctx.insert("page", &cur_page);
     ctx.insert("total_pages", &page.total_pages());
     ctx.insert("has_previous", &page.has_previous());
     ctx.insert("has_next", &page.has_next());
     ctx.insert("page_range", page.page_range());
-    ctx.insert("query_params", &Vec::<(&str, String)>::new());
+    let query_params = if include_retired {
+        vec![("retired", "true".to_string())]
+    } else {
+        vec![]
+    };
+    ctx.insert("query_params", &query_params);
+    ctx.insert("success_message", &success_message);
+    ctx.insert("error_message", &error_message);
     ctx.insert("current_menu", "vets");
 
     render(tera, "vet/vet-list.html", ctx)
 }
+
+#[get(r"/vets/{vet_id:\d+}/visits")]
+pub async fn show_vet_visits(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    query: web::Query<ShowVetListQuery>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let vet_id = path.into_inner();
+    let (cur_page, size) = page_and_size(query.page, query.size, &config.pagination)?;
+
+    let vet = VetService::fetch_vet_by_id(conn, vet_id).await?;
+    let visit_total_count = VetService::fetch_visit_count_by_vet(conn, vet_id).await?;
+    let visits = if visit_total_count > 0 {
+        VetService::fetch_visits_by_vet_paginated(conn, vet_id, cur_page, size).await?
+    } else {
+        vec![]
+    };
+
+    let page = Page::new(cur_page, size, visit_total_count);
+    let mut ctx = Context::new();
+    ctx.insert("vet", &vet);
+    ctx.insert("visits", &visits);
+    ctx.insert("page", &cur_page);
+    ctx.insert("total_pages", &page.total_pages());
+    ctx.insert("has_previous", &page.has_previous());
+    ctx.insert("has_next", &page.has_next());
+    ctx.insert("page_range", page.page_range());
+    ctx.insert("query_params", &Vec::<(&str, String)>::new());
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "vet/vet-visits.html", ctx)
+}
+
+#[get("/vets/new")]
+pub async fn init_creation_form(app_state: web::Data<AppState>) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let specialties = SpecialtyService::fetch_all_specialties(conn).await?;
+
+    let mut ctx = Context::new();
+    ctx.insert("specialties", &specialties);
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "vet/create-or-update-vet-form.html", ctx)
+}
+
+#[derive(Serialize, Validate, Default)]
+struct CreateOrUpdateVetForm {
+    #[validate(custom(function = validate_not_blank))]
+    first_name: String,
+    #[validate(custom(function = validate_not_blank))]
+    last_name: String,
+    specialties: Vec<String>,
+}
+
+impl CreateOrUpdateVetForm {
+    /// select multiple은 같은 key를 여러 번 보내므로 struct로 바로 역직렬화하지 않고 key-value 쌍에서 읽음
+    fn from_pairs(pairs: Vec<(String, String)>) -> Self {
+        let mut vet_form = Self::default();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "first_name" => vet_form.first_name = value,
+                "last_name" => vet_form.last_name = value,
+                "specialties" => vet_form.specialties.push(value),
+                _ => {}
+            }
+        }
+
+        vet_form
+    }
+
+    /// form data 검증 후에는 모든 값이 숫자임. 같은 specialty를 여러 번 보내도 한 번만 남김
+    fn specialty_ids(&self) -> Vec<i32> {
+        let mut ids: Vec<i32> = self
+            .specialties
+            .iter()
+            .filter_map(|id| id.parse().ok())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids
+    }
+
+    fn validate_with(&self, specialties: &[specialty::Model]) -> ValidationErrors {
+        let mut errors = self.validate().err().unwrap_or_default();
+
+        let all_known = self.specialties.iter().all(|id| {
+            id.parse::<i32>()
+                .is_ok_and(|id| specialties.iter().any(|s| s.id == id))
+        });
+        if !all_known {
+            errors.add(
+                "specialties",
+                create_validation_error("unknown_specialty", "notFound"),
+            );
+        }
+
+        errors
+    }
+}
+
+fn render_vet_form_with_errors(
+    req: &HttpRequest,
+    app_state: &AppState,
+    vet_id: Option<i32>,
+    vet_form: &CreateOrUpdateVetForm,
+    specialties: &[specialty::Model],
+    errors: &ValidationErrors,
+) -> Result<HttpResponse, AppError> {
+    let AppState { tera, i18n, .. } = app_state;
+
+    let mut ctx = Context::new();
+    ctx.insert("vet", vet_form);
+    ctx.insert("vet_id", &vet_id);
+    ctx.insert("specialties", specialties);
+    ctx.insert("errors", &i18n.translate_errors(req, errors));
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "vet/create-or-update-vet-form.html", ctx)
+}
+
+#[post("/vets/new")]
+pub async fn process_creation_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    form: web::Form<Vec<(String, String)>>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let vet_form = CreateOrUpdateVetForm::from_pairs(form.into_inner());
+    let specialties = SpecialtyService::fetch_all_specialties(conn).await?;
+
+    let errors = vet_form.validate_with(&specialties);
+    if !errors.is_empty() {
+        return render_vet_form_with_errors(
+            &req,
+            &app_state,
+            None,
+            &vet_form,
+            &specialties,
+            &errors,
+        );
+    }
+
+    VetService::save_vet(
+        conn,
+        Some(vet_form.first_name.clone()),
+        Some(vet_form.last_name.clone()),
+        &vet_form.specialty_ids(),
+    )
+    .await?;
+
+    FlashMessage::info("New Vet Created").send();
+
+    Ok(redirect("/vets.html".to_string()))
+}
+
+#[get(r"/vets/{vet_id:\d+}/edit")]
+pub async fn init_update_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let vet_id = path.into_inner();
+    let vet = VetService::fetch_vet_by_id(conn, vet_id).await?;
+    let specialty_ids = VetService::fetch_specialty_ids_by_vet(conn, vet_id).await?;
+    let specialties = SpecialtyService::fetch_all_specialties(conn).await?;
+
+    let vet_form = CreateOrUpdateVetForm {
+        first_name: vet.first_name.unwrap_or_default(),
+        last_name: vet.last_name.unwrap_or_default(),
+        specialties: specialty_ids.iter().map(ToString::to_string).collect(),
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("vet", &vet_form);
+    ctx.insert("vet_id", &vet_id);
+    ctx.insert("specialties", &specialties);
+    ctx.insert("current_menu", "vets");
+
+    render(tera, "vet/create-or-update-vet-form.html", ctx)
+}
+
+#[post(r"/vets/{vet_id:\d+}/edit")]
+pub async fn process_update_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+    form: web::Form<Vec<(String, String)>>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let vet_id = path.into_inner();
+    let vet_form = CreateOrUpdateVetForm::from_pairs(form.into_inner());
+    let specialties = SpecialtyService::fetch_all_specialties(conn).await?;
+
+    let errors = vet_form.validate_with(&specialties);
+    if !errors.is_empty() {
+        return render_vet_form_with_errors(
+            &req,
+            &app_state,
+            Some(vet_id),
+            &vet_form,
+            &specialties,
+            &errors,
+        );
+    }
+
+    VetService::update_vet(
+        conn,
+        vet_id,
+        Some(vet_form.first_name.clone()),
+        Some(vet_form.last_name.clone()),
+        &vet_form.specialty_ids(),
+    )
+    .await?;
+
+    FlashMessage::info("Vet Values Updated").send();
+
+    Ok(redirect("/vets.html".to_string()))
+}
+
+#[post(r"/vets/{vet_id:\d+}/retire")]
+pub async fn process_retire_vet(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let vet_id = path.into_inner();
+
+    match VetService::retire_vet(conn, vet_id).await {
+        Ok(_) => FlashMessage::info("Vet has been retired").send(),
+        Err(AppError::ResourceInUse { dependents, .. }) => FlashMessage::error(format!(
+            "Vet still has {dependents}. Reassign or cancel them first"
+        ))
+        .send(),
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect("/vets.html".to_string()))
+}
+
+#[post(r"/vets/{vet_id:\d+}/reinstate")]
+pub async fn process_reinstate_vet(
+    app_state: web::Data<AppState>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let vet_id = path.into_inner();
+    VetService::reinstate_vet(conn, vet_id).await?;
+
+    FlashMessage::info("Vet has been reinstated").send();
+
+    Ok(redirect("/vets.html?retired=true".to_string()))
+}
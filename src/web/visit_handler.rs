@@ -1,25 +1,39 @@
+use std::borrow::Cow;
+
 use actix_web::{get, post, web, HttpRequest, HttpResponse};
 use actix_web_flash_messages::FlashMessage;
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
 use sea_orm::DbConn;
 use serde::{Deserialize, Serialize};
 use tera::Context;
-use validator::Validate;
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::{
+    config::app::SchedulingConfig,
+    domain::owner::{
+        prescription::{self, PrescriptionKind},
+        visit::{self, VisitStatus},
+    },
     model::app_error::AppError,
     service::{
-        owner_service::{OwnerService, PetWithTypeAndVisits},
-        visit_service::VisitService,
+        owner_service::{OwnerService, PetWithTypeAndVisits, Visit},
+        vet_service::VetService,
+        visit_service::{Appointment, NewPrescription, VisitService},
+    },
+    web::{
+        redirect, render,
+        validator::{
+            create_validation_error, split_form_rows, validate_date_format, validate_future_date,
+            validate_not_blank, validate_time,
+        },
     },
-    web::{redirect, render, validator::validate_future_date},
     AppState,
 };
 
 #[derive(Deserialize)]
 struct OwnerWithPetPathParams {
-    owner_id: u32,
-    pet_id: u32,
+    owner_id: i32,
+    pet_id: i32,
 }
 
 #[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/new")]
@@ -27,19 +41,30 @@ pub async fn init_new_visit_form(
     app_state: web::Data<AppState>,
     path: web::Path<OwnerWithPetPathParams>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, .. } = app_state.get_ref();
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
 
     let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
 
-    let ctx = create_visit_form_context(conn, owner_id, pet_id).await?;
+    let mut ctx =
+        create_visit_form_context(conn, &config.scheduling, owner_id, pet_id, None, None, None)
+            .await?;
+    ctx.insert("is_new", &true);
 
     render(tera, "pet/create-or-update-visit-form.html", ctx)
 }
 
+/// vet 선택지는 form의 날짜(없거나 잘못된 경우 오늘)에 예약 가능한 vet으로 제한함.
+/// 수정할 때는 저장된 visit과 처치/처방의 vet이 은퇴했거나 예약이 찼더라도 선택지에 남겨둠
 async fn create_visit_form_context(
     conn: &DbConn,
-    owner_id: u32,
-    pet_id: u32,
+    scheduling: &SchedulingConfig,
+    owner_id: i32,
+    pet_id: i32,
+    date: Option<&str>,
+    exclude_visit_id: Option<i32>,
+    stored_form: Option<&CreateOrUpdateVisitForm>,
 ) -> Result<Context, AppError> {
     let owner_with_pets_and_types =
         OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
@@ -47,9 +72,50 @@ async fn create_visit_form_context(
 
     let pet = find_pet_by_id(&owner_with_pets_and_types.pets_with_type, pet_id)?;
 
+    let vets_date = date
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .unwrap_or_else(|| Local::now().date_naive());
+    let mut vets = VetService::fetch_available_vets(
+        conn,
+        vets_date,
+        scheduling.max_visits_per_vet_per_day,
+        exclude_visit_id,
+    )
+    .await?;
+    if let Some(vet_id) = stored_form.and_then(|stored| stored.vet_id.parse::<i32>().ok()) {
+        if !vets.iter().any(|v| v.id == vet_id) {
+            vets.push(VetService::fetch_vet_by_id(conn, vet_id).await?);
+        }
+    }
+    // 처방은 예약 가능 여부와 관계없이 근무 중인 vet이면 누구나 할 수 있음
+    let mut prescribing_vets = VetService::fetch_active_vets(conn).await?;
+    let stored_rows = stored_form.map(|stored| stored.prescriptions.as_slice());
+    for row in stored_rows.unwrap_or_default() {
+        let Ok(vet_id) = row.vet_id.parse::<i32>() else {
+            continue;
+        };
+        if !prescribing_vets.iter().any(|v| v.id == vet_id) {
+            prescribing_vets.push(VetService::fetch_vet_by_id(conn, vet_id).await?);
+        }
+    }
+    let durations: Vec<u32> = (1..=4).map(|n| n * scheduling.slot_minutes).collect();
+
     let mut ctx = Context::new();
     ctx.insert("owner", &owner_with_pets_and_types);
     ctx.insert("pet", &pet);
+    ctx.insert("vets", &vets);
+    ctx.insert("vets_date", &vets_date);
+    ctx.insert("prescribing_vets", &prescribing_vets);
+    ctx.insert("rooms", &scheduling.rooms);
+    ctx.insert("durations", &durations);
+    ctx.insert(
+        "opening_time",
+        &scheduling.opening_time.format("%H:%M").to_string(),
+    );
+    ctx.insert(
+        "closing_time",
+        &scheduling.closing_time.format("%H:%M").to_string(),
+    );
     ctx.insert("current_menu", "owners");
 
     Ok(ctx)
@@ -57,7 +123,7 @@ async fn create_visit_form_context(
 
 fn find_pet_by_id(
     pets: &[PetWithTypeAndVisits],
-    pet_id: u32,
+    pet_id: i32,
 ) -> Result<&PetWithTypeAndVisits, AppError> {
     pets.iter()
         .find(|p| p.pet_id == pet_id)
@@ -67,12 +133,387 @@ fn find_pet_by_id(
         })
 }
 
-#[derive(Serialize, Deserialize, Validate)]
-struct CreateVisitForm {
-    #[validate(custom(function = validate_future_date))]
+#[derive(Default, Serialize, Validate)]
+struct CreateOrUpdateVisitForm {
+    /// 새 예약이거나 예약을 바꾼 경우에만 validate_visit_form에서 지난 날짜를 거부함
+    #[validate(custom(function = validate_date_format))]
     date: String,
+    #[validate(custom(function = validate_time))]
+    time: String,
+    #[validate(range(min = 5, max = 480))]
+    duration_minutes: i32,
+    #[validate(custom(function = validate_not_blank))]
+    room: String,
     #[validate(length(min = 1, message = "공백일 수 없습니다"))]
     description: String,
+    #[validate(custom(function = validate_not_blank))]
+    vet_id: String,
+    /// 각 줄은 validate_visit_form에서 따로 검증함
+    prescriptions: Vec<PrescriptionForm>,
+}
+
+impl CreateOrUpdateVisitForm {
+    /// 저장된 visit으로 수정 form을 채움. 시각이 없는 예전 visit은 시각과 진료실을 새로 고르도록 빈 값으로 둠
+    fn from_visit(
+        visit: visit::Model,
+        prescriptions: Vec<prescription::Model>,
+        scheduling: &SchedulingConfig,
+    ) -> Self {
+        CreateOrUpdateVisitForm {
+            date: visit
+                .visit_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            time: visit
+                .starts_at
+                .map(|starts_at| starts_at.format("%H:%M").to_string())
+                .unwrap_or_default(),
+            duration_minutes: visit
+                .duration_minutes
+                .unwrap_or(scheduling.slot_minutes as i32),
+            room: visit.room.unwrap_or_default(),
+            description: visit.description.unwrap_or_default(),
+            vet_id: visit.vet_id.map(|id| id.to_string()).unwrap_or_default(),
+            prescriptions: prescriptions
+                .into_iter()
+                .map(PrescriptionForm::from_model)
+                .collect(),
+        }
+    }
+
+    /// 예약 날짜, 시각, 길이, vet, 진료실 중 하나라도 저장된 값과 다른지 확인함
+    fn is_rescheduled_from(&self, stored: &Self) -> bool {
+        self.moves_from(stored)
+            || self.time != stored.time
+            || self.duration_minutes != stored.duration_minutes
+            || self.vet_id != stored.vet_id
+            || self.room != stored.room
+    }
+
+    /// 날짜나 저장된 시각을 옮기는지 확인함. 시각이 없던 예전 visit에 시각을 채우는 것은 옮기는 것으로 보지 않음
+    fn moves_from(&self, stored: &Self) -> bool {
+        self.date != stored.date || (!stored.time.is_empty() && self.time != stored.time)
+    }
+
+    /// 처치/처방 줄은 같은 key를 여러 번 보내므로 key-value 쌍에서 읽음.
+    /// 한 줄은 rx_kind부터 시작하며, 아무것도 입력하지 않은 줄은 버림
+    fn from_pairs(pairs: Vec<(String, String)>) -> Self {
+        let (fields, rows) = split_form_rows(pairs, "rx_", "kind");
+
+        let mut visit_form = Self::default();
+        for (key, value) in fields {
+            match key.as_str() {
+                "date" => visit_form.date = value,
+                "time" => visit_form.time = value,
+                // 숫자가 아니면 범위 검증에서 걸리도록 0으로 둠
+                "duration_minutes" => visit_form.duration_minutes = value.parse().unwrap_or(0),
+                "room" => visit_form.room = value,
+                "description" => visit_form.description = value,
+                "vet_id" => visit_form.vet_id = value,
+                _ => {}
+            }
+        }
+        visit_form.prescriptions = rows
+            .into_iter()
+            .map(|mut row| PrescriptionForm {
+                kind: row.take("kind"),
+                drug: row.take("drug"),
+                dose: row.take("dose"),
+                frequency: row.take("frequency"),
+                duration_days: row.take("duration_days"),
+                vet_id: row.take("vet_id"),
+            })
+            .filter(|row| !row.is_blank())
+            .collect();
+
+        visit_form
+    }
+
+    /// form data 검증 후에만 호출하므로 모든 줄을 해석할 수 있음
+    fn new_prescriptions(&self) -> Vec<NewPrescription> {
+        self.prescriptions
+            .iter()
+            .map(|row| NewPrescription {
+                kind: row.kind().unwrap(),
+                drug: row.drug.trim().to_string(),
+                dose: row.dose.trim().to_string(),
+                frequency: row.frequency.trim().to_string(),
+                duration_days: row.duration_days.trim().parse().unwrap(),
+                vet_id: row.vet_id.parse().unwrap(),
+            })
+            .collect()
+    }
+
+    /// 날짜, 시각, vet id를 모두 해석할 수 있을 때만 Some임
+    fn appointment(&self) -> Option<Appointment> {
+        let date = NaiveDate::parse_from_str(&self.date, "%Y-%m-%d").ok()?;
+        let time = NaiveTime::parse_from_str(&self.time, "%H:%M").ok()?;
+
+        Some(Appointment {
+            starts_at: date.and_time(time),
+            duration_minutes: self.duration_minutes,
+            vet_id: self.vet_id.parse().ok()?,
+            room: self.room.clone(),
+        })
+    }
+}
+
+#[derive(Default, PartialEq, Serialize, Validate)]
+struct PrescriptionForm {
+    #[validate(custom(function = validate_prescription_kind))]
+    kind: String,
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 80, message = "80자를 넘을 수 없습니다")
+    )]
+    drug: String,
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 40, message = "40자를 넘을 수 없습니다")
+    )]
+    dose: String,
+    #[validate(
+        custom(function = validate_not_blank),
+        length(max = 40, message = "40자를 넘을 수 없습니다")
+    )]
+    frequency: String,
+    #[validate(custom(function = validate_duration_days))]
+    duration_days: String,
+    #[validate(custom(function = validate_not_blank))]
+    vet_id: String,
+}
+
+impl PrescriptionForm {
+    fn from_model(model: prescription::Model) -> Self {
+        PrescriptionForm {
+            kind: match model.kind {
+                PrescriptionKind::Treatment => "treatment",
+                PrescriptionKind::Prescription => "prescription",
+            }
+            .to_string(),
+            drug: model.drug,
+            dose: model.dose,
+            frequency: model.frequency,
+            duration_days: model.duration_days.to_string(),
+            vet_id: model.vet_id.to_string(),
+        }
+    }
+
+    fn kind(&self) -> Option<PrescriptionKind> {
+        match self.kind.as_str() {
+            "treatment" => Some(PrescriptionKind::Treatment),
+            "prescription" => Some(PrescriptionKind::Prescription),
+            _ => None,
+        }
+    }
+
+    /// 종류와 vet은 기본값이 선택되어 있으므로 나머지 칸이 모두 비어 있으면 빈 줄로 봄
+    fn is_blank(&self) -> bool {
+        [&self.drug, &self.dose, &self.frequency, &self.duration_days]
+            .iter()
+            .all(|value| value.trim().is_empty())
+    }
+}
+
+fn validate_prescription_kind(kind: &str) -> Result<(), ValidationError> {
+    if !matches!(kind, "treatment" | "prescription") {
+        return Err(create_validation_error("unknown_kind", "notFound"));
+    }
+
+    Ok(())
+}
+
+fn validate_duration_days(duration_days: &str) -> Result<(), ValidationError> {
+    match duration_days.trim().parse::<i32>() {
+        Ok(days) if (1..=365).contains(&days) => Ok(()),
+        _ => Err(create_validation_error(
+            "range",
+            "1에서 365 사이여야 합니다",
+        )),
+    }
+}
+
+/// 수정할 때는 stored_form에 저장된 visit을 넘김. 예약을 바꾸지 않았다면 이미 지난 visit이나
+/// 은퇴한 vet의 visit도 설명과 처치/처방을 고칠 수 있도록 예약 관련 검증을 건너뛰고,
+/// 지난 날짜인지는 새 예약이거나 날짜나 시각을 옮긴 경우에만 확인함
+async fn validate_visit_form(
+    conn: &DbConn,
+    scheduling: &SchedulingConfig,
+    visit_form: &CreateOrUpdateVisitForm,
+    exclude_visit_id: Option<i32>,
+    stored_form: Option<&CreateOrUpdateVisitForm>,
+) -> Result<ValidationErrors, AppError> {
+    let mut errors = visit_form.validate().err().unwrap_or_default();
+
+    let is_rescheduled = stored_form.is_none_or(|stored| visit_form.is_rescheduled_from(stored));
+    if is_rescheduled {
+        let must_be_future = stored_form.is_none_or(|stored| visit_form.moves_from(stored));
+        validate_appointment(
+            conn,
+            scheduling,
+            visit_form,
+            exclude_visit_id,
+            must_be_future,
+            &mut errors,
+        )
+        .await?;
+    }
+
+    if !visit_form.prescriptions.is_empty() {
+        let active_vets = VetService::fetch_active_vets(conn).await?;
+        let stored_rows = stored_form.map(|stored| stored.prescriptions.as_slice());
+
+        // 줄마다 rx_{줄 번호}_{칸 이름} key로 오류를 담음.
+        // 저장된 줄과 같은 줄은 처방한 vet이 그 뒤에 은퇴했더라도 그대로 둘 수 있도록 검증하지 않음
+        for (i, row) in visit_form.prescriptions.iter().enumerate() {
+            if stored_rows.unwrap_or_default().contains(row) {
+                continue;
+            }
+
+            let mut row_errors = row.validate().err().unwrap_or_default();
+
+            let is_active_vet = row
+                .vet_id
+                .parse::<i32>()
+                .is_ok_and(|vet_id| active_vets.iter().any(|v| v.id == vet_id));
+            if !row.vet_id.is_empty() && !is_active_vet {
+                row_errors.add("vet_id", create_validation_error("unknown_vet", "notFound"));
+            }
+
+            for (field, kind) in row_errors.into_errors() {
+                errors
+                    .errors_mut()
+                    .insert(Cow::Owned(format!("rx_{i}_{field}")), kind);
+            }
+        }
+    }
+
+    Ok(errors)
+}
+
+/// 진료실, 진료 시간, 그날 vet의 예약 가능 여부를 확인함.
+/// must_be_future이면 지난 날짜와 이미 지난 시각도 거부함
+async fn validate_appointment(
+    conn: &DbConn,
+    scheduling: &SchedulingConfig,
+    visit_form: &CreateOrUpdateVisitForm,
+    exclude_visit_id: Option<i32>,
+    must_be_future: bool,
+    errors: &mut ValidationErrors,
+) -> Result<(), AppError> {
+    if !visit_form.room.is_empty() && !scheduling.rooms.contains(&visit_form.room) {
+        errors.add("room", create_validation_error("unknown_room", "notFound"));
+    }
+
+    if must_be_future && !errors.field_errors().contains_key("date") {
+        if let Err(e) = validate_future_date(&visit_form.date) {
+            errors.add("date", e);
+        }
+    }
+
+    let date = NaiveDate::parse_from_str(&visit_form.date, "%Y-%m-%d");
+    if let (Ok(date), false) = (date, errors.field_errors().contains_key("vet_id")) {
+        let is_available = match visit_form.vet_id.parse::<i32>() {
+            Ok(vet_id) => VetService::fetch_available_vets(
+                conn,
+                date,
+                scheduling.max_visits_per_vet_per_day,
+                exclude_visit_id,
+            )
+            .await?
+            .iter()
+            .any(|v| v.id == vet_id),
+            Err(_) => false,
+        };
+
+        if !is_available {
+            errors.add(
+                "vet_id",
+                create_validation_error("vet_unavailable", "vetUnavailable"),
+            );
+        }
+    }
+
+    let time_is_valid = !errors.field_errors().contains_key("time");
+    if let (Some(appointment), true) = (visit_form.appointment(), time_is_valid) {
+        if must_be_future && appointment.starts_at <= Local::now().naive_local() {
+            errors.add("time", create_validation_error("past_time", "pastTime"));
+        } else if !appointment.fits_schedule(scheduling) {
+            errors.add(
+                "time",
+                create_validation_error("outside_opening_hours", "outsideOpeningHours"),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn render_visit_form_with_errors(
+    req: &HttpRequest,
+    app_state: &AppState,
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: Option<i32>,
+    stored_form: Option<&CreateOrUpdateVisitForm>,
+    visit_form: &CreateOrUpdateVisitForm,
+    errors: &ValidationErrors,
+    next_free_slots: &[NaiveDateTime],
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn,
+        tera,
+        i18n,
+        config,
+        ..
+    } = app_state;
+
+    let mut ctx = create_visit_form_context(
+        conn,
+        &config.scheduling,
+        owner_id,
+        pet_id,
+        Some(&visit_form.date),
+        visit_id,
+        stored_form,
+    )
+    .await?;
+    ctx.insert("visit", visit_form);
+    ctx.insert("is_new", &visit_id.is_none());
+
+    let translated_errors = i18n.translate_errors(req, errors);
+    ctx.insert("errors", &translated_errors);
+
+    let next_free_slots: Vec<(String, String)> = next_free_slots
+        .iter()
+        .map(|slot| {
+            (
+                slot.format("%Y-%m-%d").to_string(),
+                slot.format("%H:%M").to_string(),
+            )
+        })
+        .collect();
+    ctx.insert("next_free_slots", &next_free_slots);
+
+    render(tera, "pet/create-or-update-visit-form.html", ctx)
+}
+
+fn slot_taken_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add("time", create_validation_error("slot_taken", "slotTaken"));
+
+    errors
+}
+
+fn vet_unavailable_errors() -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "vet_id",
+        create_validation_error("vet_unavailable", "vetUnavailable"),
+    );
+
+    errors
 }
 
 #[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/new")]
@@ -80,35 +521,358 @@ pub async fn process_new_visit_form(
     req: HttpRequest,
     app_state: web::Data<AppState>,
     path: web::Path<OwnerWithPetPathParams>,
-    form: web::Form<CreateVisitForm>,
+    form: web::Form<Vec<(String, String)>>,
 ) -> Result<HttpResponse, AppError> {
-    let AppState { conn, tera, i18n } = app_state.get_ref();
+    let AppState { conn, config, .. } = app_state.get_ref();
 
     let OwnerWithPetPathParams { owner_id, pet_id } = path.into_inner();
-    let create_visit_form = form.into_inner();
+    let create_visit_form = CreateOrUpdateVisitForm::from_pairs(form.into_inner());
+
+    let errors =
+        validate_visit_form(conn, &config.scheduling, &create_visit_form, None, None).await?;
+    if !errors.is_empty() {
+        return render_visit_form_with_errors(
+            &req,
+            &app_state,
+            owner_id,
+            pet_id,
+            None,
+            None,
+            &create_visit_form,
+            &errors,
+            &[],
+        )
+        .await;
+    }
+
+    // pet이 path의 owner에 속하는지 확인함
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+    find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+
+    // form data 검증 시 확인하였으므로 반드시 Some임
+    let appointment = create_visit_form.appointment().unwrap();
+
+    match VisitService::schedule_visit(
+        conn,
+        &config.scheduling,
+        pet_id,
+        Some(create_visit_form.description.clone()),
+        appointment,
+        &create_visit_form.new_prescriptions(),
+    )
+    .await
+    {
+        Ok(_) => {}
+        // 폼을 검증한 뒤 저장하기 전에 vet이 은퇴한 경우
+        Err(AppError::InvalidResourceState { resource, .. }) if resource == "vet" => {
+            return render_visit_form_with_errors(
+                &req,
+                &app_state,
+                owner_id,
+                pet_id,
+                None,
+                None,
+                &create_visit_form,
+                &vet_unavailable_errors(),
+                &[],
+            )
+            .await;
+        }
+        Err(AppError::AppointmentConflict {
+            next_free_slots, ..
+        }) => {
+            return render_visit_form_with_errors(
+                &req,
+                &app_state,
+                owner_id,
+                pet_id,
+                None,
+                None,
+                &create_visit_form,
+                &slot_taken_errors(),
+                &next_free_slots,
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    }
 
-    if let Err(errors) = create_visit_form.validate() {
-        let mut ctx = create_visit_form_context(conn, owner_id, pet_id).await?;
-        ctx.insert("visit", &create_visit_form);
+    FlashMessage::info("Your visit has been booked").send();
 
-        let translated_errors = i18n.translate_errors(&req, &errors);
-        ctx.insert("errors", &translated_errors);
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
 
-        return render(tera, "pet/create-or-update-visit-form.html", ctx);
+/// 저장된 visit과 처치/처방으로 수정 form을 만들고 visit의 상태와 함께 반환함.
+/// visit이 path의 pet에, pet이 owner에 속하지 않으면 not found로 처리함
+async fn fetch_stored_visit_form(
+    conn: &DbConn,
+    scheduling: &SchedulingConfig,
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+) -> Result<(VisitStatus, CreateOrUpdateVisitForm), AppError> {
+    let visit = VisitService::fetch_visit_of_pet(conn, owner_id, pet_id, visit_id).await?;
+    let prescriptions = VisitService::fetch_prescriptions_by_visit(conn, visit_id).await?;
+
+    Ok((
+        visit.status,
+        CreateOrUpdateVisitForm::from_visit(visit, prescriptions, scheduling),
+    ))
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/edit")]
+pub async fn init_update_visit_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState {
+        conn, tera, config, ..
+    } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let (status, visit_form) =
+        fetch_stored_visit_form(conn, &config.scheduling, owner_id, pet_id, visit_id).await?;
+    if status == VisitStatus::Cancelled {
+        FlashMessage::error("Cancelled visits cannot be edited").send();
+
+        return Ok(redirect(format!("/owners/{owner_id}")));
     }
 
-    // form data 검증 시 확인하였으므로 반드시 Some임
-    let visit_date = NaiveDate::parse_from_str(&create_visit_form.date, "%Y-%m-%d").unwrap();
+    let mut ctx = create_visit_form_context(
+        conn,
+        &config.scheduling,
+        owner_id,
+        pet_id,
+        Some(&visit_form.date),
+        Some(visit_id),
+        Some(&visit_form),
+    )
+    .await?;
+    ctx.insert("visit", &visit_form);
+    ctx.insert("is_new", &false);
+
+    render(tera, "pet/create-or-update-visit-form.html", ctx)
+}
 
-    VisitService::save_visit(
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/edit")]
+pub async fn process_update_visit_form(
+    req: HttpRequest,
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+    form: web::Form<Vec<(String, String)>>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, config, .. } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+    let update_visit_form = CreateOrUpdateVisitForm::from_pairs(form.into_inner());
+
+    let (_, stored_form) =
+        fetch_stored_visit_form(conn, &config.scheduling, owner_id, pet_id, visit_id).await?;
+    let errors = validate_visit_form(
         conn,
-        Some(pet_id),
-        Some(visit_date),
-        Some(create_visit_form.description),
+        &config.scheduling,
+        &update_visit_form,
+        Some(visit_id),
+        Some(&stored_form),
     )
     .await?;
+    if !errors.is_empty() {
+        return render_visit_form_with_errors(
+            &req,
+            &app_state,
+            owner_id,
+            pet_id,
+            Some(visit_id),
+            Some(&stored_form),
+            &update_visit_form,
+            &errors,
+            &[],
+        )
+        .await;
+    }
 
-    FlashMessage::info("Your visit has been booked").send();
+    // form data 검증 시 확인하였으므로 반드시 Some임
+    let appointment = update_visit_form.appointment().unwrap();
+
+    match VisitService::update_visit(
+        conn,
+        owner_id,
+        pet_id,
+        visit_id,
+        &config.scheduling,
+        Some(update_visit_form.description.clone()),
+        appointment,
+        &update_visit_form.new_prescriptions(),
+    )
+    .await
+    {
+        Ok(_) => FlashMessage::info("Visit has been updated").send(),
+        Err(AppError::InvalidResourceState { resource, .. }) if resource == "vet" => {
+            return render_visit_form_with_errors(
+                &req,
+                &app_state,
+                owner_id,
+                pet_id,
+                Some(visit_id),
+                Some(&stored_form),
+                &update_visit_form,
+                &vet_unavailable_errors(),
+                &[],
+            )
+            .await;
+        }
+        Err(AppError::InvalidResourceState { .. }) => {
+            FlashMessage::error("Cancelled visits cannot be edited").send()
+        }
+        Err(AppError::AppointmentConflict {
+            next_free_slots, ..
+        }) => {
+            return render_visit_form_with_errors(
+                &req,
+                &app_state,
+                owner_id,
+                pet_id,
+                Some(visit_id),
+                Some(&stored_form),
+                &update_visit_form,
+                &slot_taken_errors(),
+                &next_free_slots,
+            )
+            .await;
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
+
+#[derive(Deserialize)]
+struct VisitPathParams {
+    owner_id: i32,
+    pet_id: i32,
+    visit_id: i32,
+}
+
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/delete")]
+pub async fn init_delete_visit_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?;
+    let pet = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+    let visit = find_visit_by_id(&pet.visits, visit_id)?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", &pet);
+    ctx.insert("visit", &visit);
+    ctx.insert("current_menu", "owners");
+
+    render(tera, "pet/delete-visit.html", ctx)
+}
+
+fn find_visit_by_id(visits: &[Visit], visit_id: i32) -> Result<&Visit, AppError> {
+    visits
+        .iter()
+        .find(|v| v.visit_id == visit_id)
+        .ok_or_else(|| AppError::ResourceNotFound {
+            resource: "visit".to_string(),
+            id: visit_id,
+        })
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/delete")]
+pub async fn process_delete_visit_form(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, storage, .. } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    match VisitService::delete_visit(conn, storage.as_ref(), owner_id, pet_id, visit_id).await {
+        Ok(()) => FlashMessage::info("Visit has been deleted").send(),
+        Err(AppError::ResourceInUse { dependents, .. }) => FlashMessage::error(format!(
+            "Visit has {dependents} on invoices and cannot be deleted"
+        ))
+        .send(),
+        Err(e) => return Err(e),
+    }
+
+    Ok(redirect(format!("/owners/{owner_id}")))
+}
+
+/// 인쇄용 처방전. 처방과 처치를 나눠 보여줌
+#[get(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/prescriptions/print")]
+pub async fn print_prescriptions(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, tera, .. } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    let owner_with_pets =
+        OwnerService::fetch_owner_with_pets_and_types_and_visits_by_owner_id(conn, owner_id)
+            .await?
+            .with_prescriptions(VisitService::fetch_prescriptions_by_owner(conn, owner_id).await?);
+    let pet = find_pet_by_id(&owner_with_pets.pets_with_type, pet_id)?;
+    let visit = find_visit_by_id(&pet.visits, visit_id)?;
+
+    let mut ctx = Context::new();
+    ctx.insert("owner", &owner_with_pets);
+    ctx.insert("pet", &pet);
+    ctx.insert("visit", &visit);
+    ctx.insert("printed_on", &Local::now().date_naive());
+
+    render(tera, "pet/print-prescriptions.html", ctx)
+}
+
+#[post(r"/owners/{owner_id:\d+}/pets/{pet_id:\d+}/visits/{visit_id:\d+}/cancel")]
+pub async fn process_cancel_visit(
+    app_state: web::Data<AppState>,
+    path: web::Path<VisitPathParams>,
+) -> Result<HttpResponse, AppError> {
+    let AppState { conn, .. } = app_state.get_ref();
+
+    let VisitPathParams {
+        owner_id,
+        pet_id,
+        visit_id,
+    } = path.into_inner();
+
+    VisitService::cancel_visit(conn, owner_id, pet_id, visit_id).await?;
+
+    FlashMessage::info("Visit has been cancelled").send();
 
     Ok(redirect(format!("/owners/{owner_id}")))
 }